@@ -21,6 +21,14 @@ pub struct Cli {
     /// Override default LLM provider
     #[arg(short, long, global = true)]
     pub provider: Option<String>,
+
+    /// Bypass the local response cache
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Use a named configuration profile (overrides GCOP_PROFILE and automatic matching)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -46,16 +54,46 @@ pub enum Commands {
         #[command(subcommand)]
         target: ReviewTarget,
 
-        /// Output format: text | json | markdown
-        #[arg(short, long, default_value = "text")]
+        /// Output format: text | json | markdown | sarif | github | gitlab | junit
+        #[arg(
+            short,
+            long,
+            default_value = "text",
+            value_parser = ["text", "json", "markdown", "sarif", "github", "gitlab", "junit"]
+        )]
         format: String,
+
+        /// Exit with a non-zero status when an issue at or above this severity is found
+        #[arg(long, value_name = "SEVERITY", value_parser = ["critical", "warning", "info"])]
+        fail_on: Option<String>,
+
+        /// Step through each issue after the review to accept, dismiss or discuss it
+        #[arg(short, long)]
+        interactive: bool,
+
+        /// Apply every suggested fix that applies cleanly to the working tree
+        #[arg(long, conflicts_with = "interactive")]
+        apply_fixes: bool,
+
+        /// Record the reported issues in .gcop/review-baseline so later reviews skip them
+        /// (with --interactive, only issues triaged as dismissed or false positive)
+        #[arg(long)]
+        update_baseline: bool,
     },
 
-    /// Initialize configuration file
+    /// Initialize configuration file (interactive setup wizard)
     Init {
         /// Force overwrite existing config
         #[arg(short, long)]
         force: bool,
+
+        /// Configure this provider without prompting: claude, openai or ollama
+        #[arg(long)]
+        provider: Option<String>,
+
+        /// Model for --provider (defaults to the provider's recommended model)
+        #[arg(long, requires = "provider")]
+        model: Option<String>,
     },
 
     /// Manage configuration
@@ -79,6 +117,29 @@ pub enum Commands {
         remove: bool,
     },
 
+    /// Manage git hooks
+    Hook {
+        #[command(subcommand)]
+        action: HookAction,
+    },
+
+    /// Manage the local LLM response cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Show LLM token usage and estimated cost
+    Usage {
+        /// Number of days to include
+        #[arg(short, long, default_value_t = 30)]
+        days: i64,
+
+        /// Output format: text | json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
     /// Show repository statistics
     Stats {
         /// Output format: text | json | markdown
@@ -93,9 +154,17 @@ pub enum Commands {
 
 #[derive(Subcommand)]
 pub enum ReviewTarget {
-    /// Review uncommitted changes
+    /// Review unstaged changes (index vs working tree)
+    #[command(visible_alias = "unstaged")]
     Changes,
 
+    /// Review staged changes (HEAD vs index), i.e. what the next commit will contain
+    Staged,
+
+    /// Review all working tree changes, staged, unstaged and untracked (HEAD vs working tree)
+    #[command(visible_alias = "all")]
+    Worktree,
+
     /// Review a specific commit
     Commit {
         /// Commit hash
@@ -104,10 +173,16 @@ pub enum ReviewTarget {
 
     /// Review a range of commits
     Range {
-        /// Commit range (e.g., main..feature)
+        /// Commit range: base..head, base...head (from the merge base), or a single base (base..HEAD)
         range: String,
     },
 
+    /// Review the current branch against its merge base with a base branch
+    Branch {
+        /// Base branch (default: the upstream, or the remote's default branch)
+        base: Option<String>,
+    },
+
     /// Review a file or directory
     File {
         /// Path to file or directory
@@ -122,4 +197,88 @@ pub enum ConfigAction {
 
     /// Validate configuration and test provider connection
     Validate,
+
+    /// Show the effective configuration and where each value comes from
+    Show {
+        /// Output format: toml | json
+        #[arg(short, long, default_value = "toml")]
+        format: String,
+    },
+
+    /// Print the effective value of a key (e.g. llm.providers.openai.model)
+    Get {
+        /// Dotted key path
+        key: String,
+    },
+
+    /// Set a key in the config file, keeping its comments and formatting
+    Set {
+        /// Dotted key path
+        key: String,
+
+        /// New value (parsed as TOML when possible, otherwise a string)
+        value: String,
+    },
+
+    /// Remove a key from the config file
+    Unset {
+        /// Dotted key path
+        key: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HookAction {
+    /// Install git hooks in the current repository
+    Install {
+        /// Hooks to install (default: prepare-commit-msg)
+        #[arg(value_parser = ["prepare-commit-msg", "commit-msg", "pre-push"])]
+        hooks: Vec<String>,
+    },
+
+    /// Remove gcop-rs hooks and restore any hooks they replaced
+    Uninstall {
+        /// Hooks to remove (default: all gcop-rs hooks)
+        #[arg(value_parser = ["prepare-commit-msg", "commit-msg", "pre-push"])]
+        hooks: Vec<String>,
+    },
+
+    /// Hook entry point called by git
+    #[command(name = "prepare-commit-msg", hide = true)]
+    PrepareCommitMsg {
+        /// File containing the commit message
+        file: String,
+
+        /// Source of the commit message
+        source: Option<String>,
+
+        /// Commit SHA (for amends)
+        sha: Option<String>,
+    },
+
+    /// Hook entry point called by git
+    #[command(name = "commit-msg", hide = true)]
+    CommitMsg {
+        /// File containing the commit message
+        file: String,
+    },
+
+    /// Hook entry point called by git
+    #[command(name = "pre-push", hide = true)]
+    PrePush {
+        /// Name of the remote being pushed to
+        remote: String,
+
+        /// URL of the remote
+        url: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// Show cache location and size
+    Info,
+
+    /// Remove all cached responses
+    Clear,
 }
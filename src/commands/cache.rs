@@ -0,0 +1,73 @@
+use crate::cli::CacheAction;
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::llm::cache::ResponseCache;
+use crate::ui;
+
+/// 管理本地响应缓存
+pub fn run(action: &CacheAction, config: &AppConfig) -> Result<()> {
+    let colored = config.ui.colored;
+    let cache = ResponseCache::from_config(&config.cache)?;
+
+    match action {
+        CacheAction::Info => {
+            let stats = cache.stats()?;
+            println!("{}", ui::info("Response cache", colored));
+            println!("  Location: {}", cache.dir().display());
+            println!(
+                "  Status:   {}",
+                if config.cache.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+            println!("  Entries:  {}", stats.entries);
+            println!(
+                "  Size:     {} / {}",
+                format_size(stats.total_bytes),
+                format_size(config.cache.max_size)
+            );
+        }
+        CacheAction::Clear => {
+            let cleared = cache.clear()?;
+            ui::success(
+                &format!(
+                    "Removed {} cached response(s) ({})",
+                    cleared.entries,
+                    format_size(cleared.total_bytes)
+                ),
+                colored,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// 格式化字节数（纯函数，便于测试）
+fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(50 * 1024 * 1024), "50.0 MB");
+    }
+}
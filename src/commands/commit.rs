@@ -4,10 +4,12 @@ use colored::Colorize;
 
 use crate::cli::Cli;
 use crate::commands::commit_state_machine::{CommitState, GenerationResult, UserAction};
+use crate::commands::preflight;
 use crate::config::AppConfig;
 use crate::error::{GcopError, Result};
 use crate::git::{DiffStats, GitOperations, repository::GitRepository};
-use crate::llm::{CommitContext, LLMProvider, provider::create_provider};
+use crate::llm::provider::{create_provider, with_cache};
+use crate::llm::{CommitContext, LLMProvider};
 use crate::ui;
 
 /// 执行 commit 命令
@@ -26,7 +28,12 @@ pub async fn run(
     dry_run: bool,
 ) -> Result<()> {
     let repo = GitRepository::open(None)?;
-    let provider = create_provider(config, cli.provider.as_deref())?;
+    let mut provider = create_provider(config, cli.provider.as_deref())?;
+
+    // dry-run 对同一 staged diff 重复执行时复用缓存；交互式重试总是重新生成
+    if dry_run && !cli.no_cache {
+        provider = with_cache(provider, config, cli.provider.as_deref())?;
+    }
 
     run_with_deps(
         cli,
@@ -64,10 +71,18 @@ async fn run_with_deps(
     let diff = repo.get_staged_diff()?;
     let stats = repo.get_diff_stats(&diff)?;
 
-    // 4. 显示预览（可选）
-    if config.commit.show_diff_preview {
-        println!("\n{}", ui::format_diff_stats(&stats, colored));
+    // 发送给 provider 前剔除排除路径并扫描密钥，再检查 token 预算
+    let diff = preflight::prepare_diff(&diff, config, colored)?;
+    let budget = preflight::check_token_budget(&diff, config, cli.provider.as_deref(), colored);
+
+    // 4. 显示预览（可选），超出预算时也先展示，便于用户缩小暂存范围
+    if config.commit.show_diff_preview || budget.is_err() {
+        println!(
+            "\n{}",
+            ui::format_diff_preview(&stats, estimated_tokens(&budget), colored)
+        );
     }
+    budget?;
 
     // dry_run 模式：只生成并输出 commit message
     if dry_run {
@@ -256,6 +271,15 @@ async fn generate_message(
     }
 }
 
+/// 预算检查得到的 token 估算值，超出预算时从错误中取出（纯函数，便于测试）
+fn estimated_tokens(budget: &Result<usize>) -> Option<usize> {
+    match budget {
+        Ok(tokens) => Some(*tokens),
+        Err(GcopError::PromptTooLarge { estimated, .. }) => Some(*estimated),
+        Err(_) => None,
+    }
+}
+
 /// 格式化消息头部（纯函数，便于测试）
 fn format_message_header(attempt: usize) -> String {
     if attempt == 0 {
@@ -297,6 +321,21 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    // === estimated_tokens 测试 ===
+
+    #[test]
+    fn test_estimated_tokens_kept_when_over_budget() {
+        assert_eq!(estimated_tokens(&Ok(1_234)), Some(1_234));
+        assert_eq!(
+            estimated_tokens(&Err(GcopError::PromptTooLarge {
+                estimated: 250_000,
+                limit: 198_000,
+            })),
+            Some(250_000)
+        );
+        assert_eq!(estimated_tokens(&Err(GcopError::NoStagedChanges)), None);
+    }
+
     // === format_message_header 测试 ===
 
     #[test]
@@ -1,6 +1,7 @@
 use crate::config::{self, load_config};
 use crate::error::{GcopError, Result};
 use crate::llm::provider::create_provider;
+use crate::llm::provider::credentials::{api_key_env_var, resolve_api_key};
 use crate::ui;
 use colored::Colorize;
 use dialoguer::Select;
@@ -12,13 +13,27 @@ enum EditAction {
     Ignore, // 忽略错误强制保存
 }
 
-pub async fn run(action: Option<crate::cli::ConfigAction>, colored: bool) -> Result<()> {
+pub async fn run(
+    action: Option<crate::cli::ConfigAction>,
+    profile: Option<&str>,
+    colored: bool,
+) -> Result<()> {
     // 默认行为：调用 edit
     let action = action.unwrap_or(crate::cli::ConfigAction::Edit);
 
     match action {
         crate::cli::ConfigAction::Edit => edit(colored),
-        crate::cli::ConfigAction::Validate => validate(colored).await,
+        crate::cli::ConfigAction::Validate => validate(profile, colored).await,
+        crate::cli::ConfigAction::Show { format } => {
+            crate::commands::config_show::run(profile, &format, colored)
+        }
+        crate::cli::ConfigAction::Get { key } => crate::commands::config_keys::get(profile, &key),
+        crate::cli::ConfigAction::Set { key, value } => {
+            crate::commands::config_keys::set(&key, &value, colored)
+        }
+        crate::cli::ConfigAction::Unset { key } => {
+            crate::commands::config_keys::unset(&key, colored)
+        }
     }
 }
 
@@ -57,7 +72,18 @@ fn edit(colored: bool) -> Result<()> {
             edit::edit(&content).map_err(|e| GcopError::Other(format!("Editor error: {}", e)))?;
 
         // 校验配置（直接在内存校验）
-        match toml::from_str::<config::AppConfig>(&edited) {
+        let result = toml::from_str::<config::AppConfig>(&edited)
+            .map_err(|e| e.to_string())
+            .and_then(|_| {
+                let issues = config::check::check_document(&edited);
+                if issues.is_empty() {
+                    return Ok(());
+                }
+                let lines: Vec<String> =
+                    issues.iter().map(|issue| format!("  {}", issue)).collect();
+                Err(format!("\n{}", lines.join("\n")))
+            });
+        match result {
             Ok(_) => {
                 // 校验成功，写入文件
                 std::fs::write(&config_file, &edited)?;
@@ -139,15 +165,29 @@ fn prompt_edit_action(colored: bool) -> Result<EditAction> {
 }
 
 /// 验证配置
-async fn validate(colored: bool) -> Result<()> {
+async fn validate(profile: Option<&str>, colored: bool) -> Result<()> {
     ui::step("1/2", "Loading configuration...", colored);
 
     // 加载配置
-    let config = load_config()?;
+    let config = load_config(profile)?;
 
     ui::success("Configuration loaded successfully", colored);
     println!();
 
+    if !config.profiles.is_empty() {
+        let mut names: Vec<&String> = config.profiles.keys().collect();
+        names.sort();
+        println!("Profiles:");
+        for name in names {
+            if config.active_profile.as_ref() == Some(name) {
+                println!("  • {} (active)", name);
+            } else {
+                println!("  • {}", name);
+            }
+        }
+        println!();
+    }
+
     // 显示配置的 providers
     println!("Configured providers:");
     for name in config.llm.providers.keys() {
@@ -155,6 +195,19 @@ async fn validate(colored: bool) -> Result<()> {
     }
     println!();
 
+    // 显示默认 provider 的 API key 来源（不输出 key 本身）
+    let name = &config.llm.default_provider;
+    if let Some(provider_config) = config.llm.providers.get(name) {
+        let api_style = provider_config.api_style.as_deref().unwrap_or(name);
+        if let Some(env_var) = api_key_env_var(api_style) {
+            match resolve_api_key(provider_config, Some(env_var))? {
+                Some((_, source)) => println!("API key for '{}': {}", name, source),
+                None => println!("API key for '{}': not set", name),
+            }
+            println!();
+        }
+    }
+
     // 测试默认 provider 连接
     ui::step("2/2", "Testing default provider connection...", colored);
 
@@ -0,0 +1,298 @@
+//! `config get` / `config set` / `config unset`
+//!
+//! 按点分路径（如 `llm.providers.openai.model`）读取生效配置，或修改用户配置文件。
+//! 修改通过 toml_edit 进行，保留文件中的注释和格式；写入前用 `AppConfig` 和 schema 校验，
+//! 类型不符、键不存在或取值非法时不修改文件。
+
+use std::path::Path;
+use std::str::FromStr;
+
+use toml_edit::{DocumentMut, Item, Key, Value};
+
+use crate::commands::config_show::redacted_table;
+use crate::config::check::check_document;
+use crate::config::{self, AppConfig, load_config_with_sources};
+use crate::error::{GcopError, Result};
+use crate::ui;
+
+/// 输出键的生效值（密钥会被隐藏），字符串原样输出，其他值按 TOML 输出
+pub fn get(profile: Option<&str>, key: &str) -> Result<()> {
+    let loaded = load_config_with_sources(profile)?;
+    let table = redacted_table(&loaded)?;
+    let mut current = &toml::Value::Table(table);
+    for segment in parse_key(key)? {
+        current = current
+            .get(segment.get())
+            .ok_or_else(|| GcopError::InvalidInput(format!("Key '{}' is not set", key)))?;
+    }
+    match current {
+        toml::Value::String(s) => println!("{}", s),
+        toml::Value::Table(t) => print!("{}", toml::to_string(t).unwrap_or_default()),
+        other => println!("{}", other),
+    }
+    Ok(())
+}
+
+/// 设置用户配置文件中的键
+pub fn set(key: &str, value: &str, colored: bool) -> Result<()> {
+    let path = config_file()?;
+    let content = read_or_empty(&path)?;
+    let updated = set_value(&content, key, value)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    config::write_config_file(&path, &updated)?;
+    ui::success(&format!("Set {} in {}", key, path.display()), colored);
+    Ok(())
+}
+
+/// 删除用户配置文件中的键
+pub fn unset(key: &str, colored: bool) -> Result<()> {
+    let path = config_file()?;
+    let content = read_or_empty(&path)?;
+    let updated = unset_value(&content, key)?;
+    config::write_config_file(&path, &updated)?;
+    ui::success(&format!("Removed {} from {}", key, path.display()), colored);
+    Ok(())
+}
+
+fn config_file() -> Result<std::path::PathBuf> {
+    config::get_config_dir()
+        .map(|dir| dir.join("config.toml"))
+        .ok_or_else(|| GcopError::Config("Failed to determine config directory".to_string()))
+}
+
+fn read_or_empty(path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 解析点分路径，支持带引号的段（如 `usage.prices."gpt-4.1".input`）
+fn parse_key(key: &str) -> Result<Vec<Key>> {
+    Key::parse(key).map_err(|e| GcopError::InvalidInput(format!("Invalid key '{}': {}", key, e)))
+}
+
+fn parse_document(content: &str) -> Result<DocumentMut> {
+    DocumentMut::from_str(content)
+        .map_err(|e| GcopError::Config(format!("Failed to parse config file: {}", e)))
+}
+
+/// 在文档中设置值，返回新内容
+///
+/// 值先按 TOML 解析（`10`、`true`、`["a", "b"]`），不合法或不符合 schema 时再按字符串处理
+pub fn set_value(content: &str, key: &str, raw: &str) -> Result<String> {
+    let path = parse_key(key)?;
+    let mut candidates = Vec::new();
+    if let Ok(parsed) = Value::from_str(raw) {
+        candidates.push(parsed);
+    }
+    candidates.push(Value::from(raw));
+
+    // 只报告与本次设置的键有关的 schema 问题，文件中已有的问题不影响设置
+    let dotted = path.iter().map(Key::get).collect::<Vec<_>>().join(".");
+    let mut first_error = None;
+    for value in candidates {
+        let mut doc = parse_document(content)?;
+        insert(&mut doc, &path, value, key)?;
+        let updated = doc.to_string();
+        if let Err(e) = toml::from_str::<AppConfig>(&updated) {
+            first_error.get_or_insert(e.message().to_string());
+            continue;
+        }
+        let issue = check_document(&updated)
+            .into_iter()
+            .find(|issue| issue.key == dotted || issue.key.starts_with(&format!("{}.", dotted)));
+        match issue {
+            Some(issue) if issue.key == dotted => {
+                first_error.get_or_insert(issue.message);
+            }
+            Some(issue) => {
+                first_error.get_or_insert(format!("{}: {}", issue.key, issue.message));
+            }
+            None => return Ok(updated),
+        }
+    }
+    Err(GcopError::InvalidInput(format!(
+        "Invalid value for {}: {}",
+        key,
+        first_error.unwrap_or_default()
+    )))
+}
+
+fn insert(doc: &mut DocumentMut, path: &[Key], mut value: Value, key: &str) -> Result<()> {
+    let (last, parents) = path
+        .split_last()
+        .ok_or_else(|| GcopError::InvalidInput("Empty key".to_string()))?;
+    let mut item = doc.as_item_mut();
+    for segment in parents {
+        let table = item
+            .as_table_like_mut()
+            .ok_or_else(|| not_a_table(key, segment))?;
+        if table.get(segment.get()).is_none() {
+            let mut child = toml_edit::Table::new();
+            child.set_implicit(true);
+            table.insert(segment.get(), Item::Table(child));
+        }
+        item = table
+            .get_mut(segment.get())
+            .ok_or_else(|| not_a_table(key, segment))?;
+    }
+
+    let table = item
+        .as_table_like_mut()
+        .ok_or_else(|| not_a_table(key, last))?;
+    match table.get_mut(last.get()) {
+        Some(Item::Value(existing)) => {
+            // 保留原值前后的空白和行尾注释
+            *value.decor_mut() = existing.decor().clone();
+            *existing = value;
+        }
+        Some(Item::Table(_)) | Some(Item::ArrayOfTables(_)) => {
+            return Err(GcopError::InvalidInput(format!(
+                "'{}' is a table; set one of its keys instead",
+                key
+            )));
+        }
+        _ => {
+            table.insert(last.get(), Item::Value(value));
+        }
+    }
+    Ok(())
+}
+
+fn not_a_table(key: &str, segment: &Key) -> GcopError {
+    GcopError::InvalidInput(format!(
+        "Cannot set '{}': '{}' is not a table",
+        key,
+        segment.get()
+    ))
+}
+
+/// 从文档中删除键，返回新内容
+///
+/// 路径上因此变空的表也会一并删除
+pub fn unset_value(content: &str, key: &str) -> Result<String> {
+    let path = parse_key(key)?;
+    let mut doc = parse_document(content)?;
+    if !remove_path(doc.as_item_mut(), &path) {
+        return Err(GcopError::InvalidInput(format!(
+            "Key '{}' is not set in config file",
+            key
+        )));
+    }
+
+    let updated = doc.to_string();
+    toml::from_str::<AppConfig>(&updated)
+        .map_err(|e| GcopError::InvalidInput(format!("Cannot unset {}: {}", key, e.message())))?;
+    Ok(updated)
+}
+
+/// 删除 `item` 下的路径，返回是否删除成功
+fn remove_path(item: &mut Item, path: &[Key]) -> bool {
+    let Some(table) = item.as_table_like_mut() else {
+        return false;
+    };
+    match path {
+        [] => false,
+        [last] => table.remove(last.get()).is_some(),
+        [first, rest @ ..] => {
+            let Some(child) = table.get_mut(first.get()) else {
+                return false;
+            };
+            if !remove_path(child, rest) {
+                return false;
+            }
+            if child.as_table_like().is_some_and(|t| t.is_empty()) {
+                table.remove(first.get());
+            }
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const CONFIG: &str = r#"# gcop-rs config
+[llm]
+default_provider = "claude"  # switch per machine
+
+[llm.providers.claude]
+api_key = "sk-ant"
+model = "claude-sonnet"
+
+[commit]
+max_retries = 10
+"#;
+
+    #[test]
+    fn test_set_preserves_comments_and_types() {
+        let updated = set_value(CONFIG, "llm.default_provider", "openai").unwrap();
+        assert!(updated.contains("default_provider = \"openai\"  # switch per machine\n"));
+        assert!(updated.starts_with("# gcop-rs config\n"));
+
+        let updated = set_value(&updated, "commit.max_retries", "3").unwrap();
+        assert!(updated.contains("max_retries = 3\n"));
+
+        let updated = set_value(&updated, "convention.scopes", r#"["cli", "core"]"#).unwrap();
+        let config: AppConfig = toml::from_str(&updated).unwrap();
+        assert_eq!(config.convention.scopes, vec!["cli", "core"]);
+        assert_eq!(config.commit.max_retries, 3);
+    }
+
+    #[test]
+    fn test_set_creates_tables() {
+        let updated = set_value("", "llm.providers.openai.model", "gpt-4o").unwrap();
+        assert_eq!(updated, "[llm.providers.openai]\nmodel = \"gpt-4o\"\n");
+
+        // 值像数字但字段是字符串时按字符串处理
+        let updated = set_value(&updated, "llm.providers.openai.model", "123").unwrap();
+        assert!(updated.contains("model = \"123\""));
+    }
+
+    #[test]
+    fn test_set_rejects_wrong_type() {
+        let err = set_value(CONFIG, "commit.max_retries", "many").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Invalid value for commit.max_retries")
+        );
+
+        let err = set_value(CONFIG, "llm.providers", "x").unwrap_err();
+        assert!(err.to_string().contains("is a table"));
+    }
+
+    #[test]
+    fn test_set_rejects_schema_issues() {
+        let err = set_value(CONFIG, "commit.max_retry", "3").unwrap_err();
+        assert!(err.to_string().contains("did you mean 'max_retries'?"));
+
+        let err = set_value(CONFIG, "review.min_severity", "high").unwrap_err();
+        assert!(err.to_string().contains("must be one of"));
+
+        let err = set_value(CONFIG, "llm.providers.claude.temperature", "3").unwrap_err();
+        assert!(err.to_string().contains("must be between 0 and 2"));
+
+        // 文件中其他位置已有的问题不影响设置
+        let broken = format!("{}\n[ui]\nshiny = true\n", CONFIG);
+        assert!(set_value(&broken, "commit.max_retries", "3").is_ok());
+    }
+
+    #[test]
+    fn test_unset() {
+        let updated = unset_value(CONFIG, "commit.max_retries").unwrap();
+        assert!(!updated.contains("max_retries"));
+        // 变空的表一并删除
+        assert!(!updated.contains("[commit]"));
+        assert!(updated.contains("# switch per machine"));
+
+        assert!(unset_value(CONFIG, "commit.allow_edit").is_err());
+        // 删除必填字段会让配置失效
+        assert!(unset_value(CONFIG, "llm.providers.claude.model").is_err());
+    }
+}
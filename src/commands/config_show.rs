@@ -0,0 +1,265 @@
+//! `config show`：输出合并后的生效配置
+//!
+//! 每个值标注来源（默认值、用户配置、profile、`.gcop.toml` 或环境变量），
+//! API key 等密钥替换为 `<redacted>`。
+
+use colored::Colorize;
+
+use crate::config::sources::join_key;
+use crate::config::{LoadedConfig, load_config_with_sources};
+use crate::error::{GcopError, Result};
+
+/// 密钥值的替代文本
+const REDACTED: &str = "<redacted>";
+
+/// 来源注释对齐的最大列宽
+const MAX_ALIGN_WIDTH: usize = 48;
+
+pub fn run(profile: Option<&str>, format: &str, colored: bool) -> Result<()> {
+    let loaded = load_config_with_sources(profile)?;
+    let output = match format {
+        "toml" => render_toml(&loaded, colored)?,
+        "json" => render_json(&loaded)?,
+        other => {
+            return Err(GcopError::InvalidInput(format!(
+                "Unknown format '{}' (expected toml or json)",
+                other
+            )));
+        }
+    };
+    print!("{}", output);
+    Ok(())
+}
+
+/// 渲染为 TOML，每个值后用注释标注来源
+pub fn render_toml(loaded: &LoadedConfig, colored: bool) -> Result<String> {
+    let table = redacted_table(loaded)?;
+    let mut out = String::new();
+    if let Some(profile) = &loaded.config.active_profile {
+        out.push_str(&format!("# Active profile: {}\n\n", profile));
+    }
+    render_section(&table, "", &[], loaded, colored, &mut out);
+    Ok(out)
+}
+
+/// 渲染为 JSON：`config` 为生效配置，`sources` 为每个键的来源
+pub fn render_json(loaded: &LoadedConfig) -> Result<String> {
+    let table = redacted_table(loaded)?;
+    let mut sources = serde_json::Map::new();
+    for key in leaf_keys(&table, "") {
+        let source = loaded.source_of(&key).to_string();
+        sources.insert(key, serde_json::Value::String(source));
+    }
+    let output = serde_json::json!({
+        "active_profile": loaded.config.active_profile,
+        "config": table,
+        "sources": sources,
+    });
+    Ok(format!("{}\n", serde_json::to_string_pretty(&output)?))
+}
+
+/// 序列化配置并隐藏密钥
+pub fn redacted_table(loaded: &LoadedConfig) -> Result<toml::Table> {
+    let mut table = toml::Table::try_from(&loaded.config)
+        .map_err(|e| GcopError::Other(format!("Failed to serialize config: {}", e)))?;
+    redact(&mut table);
+    Ok(table)
+}
+
+fn redact(table: &mut toml::Table) {
+    for (key, value) in table.iter_mut() {
+        match value {
+            toml::Value::Table(child) => redact(child),
+            _ if is_secret_key(key) => *value = toml::Value::String(REDACTED.to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// `api_key`、`*_key`、`*_token`、`*_secret`、`*password*` 视为密钥
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key == "token"
+        || key == "secret"
+        || key.ends_with("_key")
+        || key.ends_with("_token")
+        || key.ends_with("_secret")
+        || key.contains("password")
+}
+
+fn leaf_keys(table: &toml::Table, prefix: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    for (key, value) in table {
+        let path = join_key(prefix, key);
+        match value {
+            toml::Value::Table(child) => keys.extend(leaf_keys(child, &path)),
+            _ => keys.push(path),
+        }
+    }
+    keys
+}
+
+/// 渲染一个表：先输出本表的值（对齐来源注释），再递归输出子表
+fn render_section(
+    table: &toml::Table,
+    path: &str,
+    header: &[String],
+    loaded: &LoadedConfig,
+    colored: bool,
+    out: &mut String,
+) {
+    let lines: Vec<(String, &str)> = table
+        .iter()
+        .filter(|(_, value)| !value.is_table())
+        .map(|(key, value)| {
+            (
+                format!("{} = {}", toml_key(key), value),
+                loaded.source_of(&join_key(path, key)),
+            )
+        })
+        .collect();
+
+    if !lines.is_empty() {
+        if !header.is_empty() {
+            out.push_str(&format!("[{}]\n", header.join(".")));
+        }
+        // 过长的行（如长数组）不参与对齐，避免注释被推得太远
+        let width = lines
+            .iter()
+            .map(|(line, _)| line.len())
+            .filter(|len| *len <= MAX_ALIGN_WIDTH)
+            .max()
+            .unwrap_or(0);
+        for (line, source) in lines {
+            let comment = format!("# {}", source);
+            let comment = if colored {
+                comment.bright_black().to_string()
+            } else {
+                comment
+            };
+            out.push_str(&format!("{:<width$}  {}\n", line, comment, width = width));
+        }
+        out.push('\n');
+    }
+
+    for (key, value) in table {
+        if let toml::Value::Table(child) = value {
+            let mut child_header = header.to_vec();
+            child_header.push(toml_key(key));
+            render_section(
+                child,
+                &join_key(path, key),
+                &child_header,
+                loaded,
+                colored,
+                out,
+            );
+        }
+    }
+}
+
+/// 需要时给键加引号
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        toml::Value::String(key.to_string()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AppConfig, ConfigLayer, ProviderConfig};
+    use pretty_assertions::assert_eq;
+
+    fn loaded() -> LoadedConfig {
+        let mut config = AppConfig::default();
+        config.llm.providers.insert(
+            "claude".to_string(),
+            ProviderConfig {
+                api_style: None,
+                endpoint: None,
+                api_key: Some("sk-ant-secret".to_string()),
+                api_key_file: None,
+                api_key_cmd: None,
+                model: "claude-sonnet".to_string(),
+                max_tokens: Some(2000),
+                temperature: None,
+                extra: [(
+                    "auth_token".to_string(),
+                    serde_json::Value::String("tok".to_string()),
+                )]
+                .into(),
+            },
+        );
+        config.commit.max_retries = 3;
+        let user: toml::Table = toml::from_str(
+            "[llm.providers.claude]\napi_key = \"sk-ant-secret\"\nmodel = \"claude-sonnet\"\n",
+        )
+        .unwrap();
+        LoadedConfig {
+            config,
+            layers: vec![
+                ConfigLayer::from_table("config.toml", &user),
+                ConfigLayer {
+                    label: "env GCOP_COMMIT_MAX_RETRIES".to_string(),
+                    keys: ["commit.max_retries".to_string()].into(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_toml_annotates_and_redacts() {
+        let output = render_toml(&loaded(), false).unwrap();
+        assert!(!output.contains("sk-ant-secret"));
+        assert!(!output.contains("\"tok\""));
+
+        let section = output
+            .split("[llm.providers.claude]\n")
+            .nth(1)
+            .unwrap()
+            .split("\n\n")
+            .next()
+            .unwrap();
+        assert_eq!(
+            section,
+            "api_key = \"<redacted>\"     # config.toml\n\
+             auth_token = \"<redacted>\"  # default\n\
+             max_tokens = 2000          # default\n\
+             model = \"claude-sonnet\"    # config.toml"
+        );
+        assert!(output.contains("max_retries = 3"));
+        assert!(output.contains("# env GCOP_COMMIT_MAX_RETRIES\n"));
+
+        // 输出本身是合法的 TOML
+        assert!(toml::from_str::<toml::Table>(&output).is_ok());
+    }
+
+    #[test]
+    fn test_render_json() {
+        let output = render_json(&loaded()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(
+            json["config"]["llm"]["providers"]["claude"]["api_key"],
+            REDACTED
+        );
+        assert_eq!(json["sources"]["llm.providers.claude.model"], "config.toml");
+        assert_eq!(json["sources"]["ui.colored"], "default");
+        assert!(!output.contains("sk-ant-secret"));
+    }
+
+    #[test]
+    fn test_is_secret_key() {
+        assert!(is_secret_key("api_key"));
+        assert!(is_secret_key("Access_Token"));
+        assert!(!is_secret_key("max_tokens"));
+        assert!(!is_secret_key("api_key_cmd"));
+        assert!(!is_secret_key("api_key_file"));
+    }
+}
@@ -0,0 +1,708 @@
+//! git hook 集成
+//!
+//! `hook install` 在当前仓库安装 `prepare-commit-msg` hook，
+//! 使 IDE 等直接调用 `git commit` 的场景也能自动生成 commit message；
+//! `commit-msg` hook 按 `[convention]` 检查手写的 commit message；
+//! `pre-push` hook 审查待推送的 commit，发现严重问题时阻止推送。
+//! 已存在的 hook 会被重命名为 `<name>.pre-gcop` 并由新 hook 先行调用。
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use git2::Repository;
+
+use crate::cli::{Cli, HookAction};
+use crate::commands::review_rules::ReviewRules;
+use crate::commands::{preflight, review};
+use crate::config::AppConfig;
+use crate::error::{GcopError, Result};
+use crate::git::commit_lint::{LintViolation, describe_rules, lint_message, strip_comments};
+use crate::git::{GitOperations, repository::GitRepository};
+use crate::llm::prompt::build_rewrite_prompt;
+use crate::llm::provider::{create_provider, with_cache};
+use crate::llm::{CommitContext, IssueSeverity, LLMProvider, ReviewType};
+use crate::ui;
+
+/// gcop-rs 管理的 hook 文件标记
+const HOOK_MARKER: &str = "# Installed by gcop-rs";
+
+/// 被替换的原有 hook 的后缀
+const BACKUP_SUFFIX: &str = ".pre-gcop";
+
+/// prepare-commit-msg hook 名称
+const PREPARE_COMMIT_MSG: &str = "prepare-commit-msg";
+
+/// commit-msg hook 名称
+const COMMIT_MSG: &str = "commit-msg";
+
+/// pre-push hook 名称
+const PRE_PUSH: &str = "pre-push";
+
+/// 跳过 pre-push 审查的环境变量
+const SKIP_REVIEW_ENV: &str = "GCOP_SKIP_REVIEW";
+
+/// hook 行为描述
+struct HookSpec {
+    name: &'static str,
+    /// 失败时是否阻止 git 操作
+    blocking: bool,
+    /// git 是否通过 stdin 传入数据
+    reads_stdin: bool,
+}
+
+/// 支持的 hook
+const SUPPORTED_HOOKS: &[HookSpec] = &[
+    HookSpec {
+        name: PREPARE_COMMIT_MSG,
+        blocking: false,
+        reads_stdin: false,
+    },
+    HookSpec {
+        name: COMMIT_MSG,
+        blocking: true,
+        reads_stdin: false,
+    },
+    HookSpec {
+        name: PRE_PUSH,
+        blocking: true,
+        reads_stdin: true,
+    },
+];
+
+/// 安装结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallOutcome {
+    /// 新安装
+    Installed,
+    /// 已有 hook 被保留为备份并串联调用
+    Chained(PathBuf),
+    /// 已安装，脚本已更新
+    Updated,
+}
+
+/// 卸载结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UninstallOutcome {
+    /// 已移除，`true` 表示恢复了原有 hook
+    Removed(bool),
+    /// 未安装
+    NotInstalled,
+    /// 存在同名 hook，但不是 gcop-rs 安装的
+    Foreign,
+}
+
+/// 执行 hook 命令
+pub async fn run(cli: &Cli, action: &HookAction, config: &AppConfig) -> Result<()> {
+    let colored = config.ui.colored;
+
+    match action {
+        HookAction::Install { hooks } => {
+            let repo = Repository::open_from_env()?;
+            let dir = hooks_dir(&repo)?;
+            let names = selected_hooks(hooks, &[PREPARE_COMMIT_MSG]);
+            for name in names {
+                let path = dir.join(name);
+                match install_hook(&dir, name)? {
+                    InstallOutcome::Installed => {
+                        ui::success(&format!("Installed hook: {}", path.display()), colored);
+                    }
+                    InstallOutcome::Updated => {
+                        ui::success(&format!("Updated hook: {}", path.display()), colored);
+                    }
+                    InstallOutcome::Chained(backup) => {
+                        ui::success(&format!("Installed hook: {}", path.display()), colored);
+                        println!(
+                            "{}",
+                            ui::info(
+                                &format!(
+                                    "Existing hook moved to {} and will still run first",
+                                    backup.display()
+                                ),
+                                colored
+                            )
+                        );
+                    }
+                }
+            }
+            Ok(())
+        }
+        HookAction::Uninstall { hooks } => {
+            let repo = Repository::open_from_env()?;
+            let dir = hooks_dir(&repo)?;
+            let explicit = !hooks.is_empty();
+            let all: Vec<&str> = SUPPORTED_HOOKS.iter().map(|spec| spec.name).collect();
+            for name in selected_hooks(hooks, &all) {
+                match uninstall_hook(&dir, name)? {
+                    UninstallOutcome::Removed(restored) => {
+                        ui::success(&format!("Removed {} hook", name), colored);
+                        if restored {
+                            println!(
+                                "{}",
+                                ui::info(&format!("Restored the previous {} hook", name), colored)
+                            );
+                        }
+                    }
+                    UninstallOutcome::NotInstalled => {
+                        if explicit {
+                            ui::warning(
+                                &format!("gcop-rs {} hook is not installed.", name),
+                                colored,
+                            );
+                        }
+                    }
+                    UninstallOutcome::Foreign => {
+                        ui::warning(
+                            &format!(
+                                "{} hook was not installed by gcop-rs, leaving it untouched.",
+                                name
+                            ),
+                            colored,
+                        );
+                    }
+                }
+            }
+            Ok(())
+        }
+        HookAction::PrepareCommitMsg { file, source, .. } => {
+            // hook 失败不能阻止提交，只输出警告
+            if let Err(e) = prepare_commit_msg(cli, config, file, source.as_deref()).await {
+                eprintln!("gcop-rs: skipped commit message generation: {}", e);
+            }
+            Ok(())
+        }
+        HookAction::CommitMsg { file } => commit_msg(cli, config, file).await,
+        HookAction::PrePush { remote, .. } => pre_push(cli, config, remote).await,
+    }
+}
+
+/// 用户指定的 hook 列表，未指定时使用默认值
+fn selected_hooks<'a>(requested: &'a [String], default: &[&'a str]) -> Vec<&'a str> {
+    if requested.is_empty() {
+        default.to_vec()
+    } else {
+        requested.iter().map(String::as_str).collect()
+    }
+}
+
+/// 解析 hooks 目录（遵循 `core.hooksPath`，相对路径基于工作区根目录）
+pub fn hooks_dir(repo: &Repository) -> Result<PathBuf> {
+    if let Ok(path) = repo.config()?.get_path("core.hooksPath") {
+        if path.is_absolute() {
+            return Ok(path);
+        }
+        let base = repo.workdir().unwrap_or_else(|| repo.path());
+        return Ok(base.join(path));
+    }
+    Ok(repo.commondir().join("hooks"))
+}
+
+/// 生成 hook 脚本
+///
+/// 阻塞型 hook（如 commit-msg）的退出码会传递给 git，其余 hook 的失败被忽略；
+/// 需要 stdin 的 hook（如 pre-push）先缓存输入，再分别传给原有 hook 和 gcop-rs
+fn hook_script(name: &str) -> String {
+    let spec = SUPPORTED_HOOKS.iter().find(|spec| spec.name == name);
+    let blocking = spec.is_some_and(|spec| spec.blocking);
+    let reads_stdin = spec.is_some_and(|spec| spec.reads_stdin);
+
+    let (read_input, feed) = if reads_stdin {
+        ("input=\"$(cat)\"\n", "printf '%s\\n' \"$input\" | ")
+    } else {
+        ("", "")
+    };
+    let redirect = if reads_stdin { "" } else { " </dev/null" };
+    let on_failure = if blocking { "" } else { " || true" };
+
+    format!(
+        r#"#!/bin/sh
+{HOOK_MARKER}. Remove with `gcop-rs hook uninstall`.
+{read_input}
+# Run the hook this one replaced, if any
+previous="$(dirname "$0")/{name}{BACKUP_SUFFIX}"
+if [ -x "$previous" ]; then
+    {feed}"$previous" "$@" || exit $?
+fi
+
+command -v gcop-rs >/dev/null 2>&1 || exit 0
+{feed}gcop-rs hook {name} "$@"{redirect}{on_failure}
+"#
+    )
+}
+
+/// 判断 hook 文件是否由 gcop-rs 安装
+fn is_gcop_hook(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|content| content.contains(HOOK_MARKER))
+        .unwrap_or(false)
+}
+
+/// 在指定目录安装 hook，已有的非 gcop-rs hook 会被重命名并串联调用
+pub fn install_hook(dir: &Path, name: &str) -> Result<InstallOutcome> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(name);
+    let backup = dir.join(format!("{}{}", name, BACKUP_SUFFIX));
+
+    let outcome = if !path.exists() {
+        InstallOutcome::Installed
+    } else if is_gcop_hook(&path) {
+        InstallOutcome::Updated
+    } else {
+        if backup.exists() {
+            return Err(GcopError::Other(format!(
+                "Cannot chain existing {} hook: {} already exists",
+                name,
+                backup.display()
+            )));
+        }
+        fs::rename(&path, &backup)?;
+        InstallOutcome::Chained(backup)
+    };
+
+    fs::write(&path, hook_script(name))?;
+    make_executable(&path)?;
+    Ok(outcome)
+}
+
+/// 卸载 hook，并恢复被替换的原有 hook
+pub fn uninstall_hook(dir: &Path, name: &str) -> Result<UninstallOutcome> {
+    let path = dir.join(name);
+    if !path.exists() {
+        return Ok(UninstallOutcome::NotInstalled);
+    }
+    if !is_gcop_hook(&path) {
+        return Ok(UninstallOutcome::Foreign);
+    }
+
+    fs::remove_file(&path)?;
+    let backup = dir.join(format!("{}{}", name, BACKUP_SUFFIX));
+    let restored = backup.exists();
+    if restored {
+        fs::rename(&backup, &path)?;
+    }
+    Ok(UninstallOutcome::Removed(restored))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// 根据 git 传入的消息来源判断是否需要生成
+///
+/// 来源为 message（-m/-F）、merge、squash、commit（--amend/-c/-C）时跳过
+fn should_generate(source: Option<&str>) -> bool {
+    matches!(source, None | Some("") | Some("template"))
+}
+
+/// 消息文件中是否已有非注释内容（如模板或其他 hook 写入的内容）
+///
+/// `git commit -v` 会在剪刀线（`# ---- >8 ----`）之后附加 diff，这部分不算消息内容
+fn has_message_content(content: &str) -> bool {
+    content
+        .lines()
+        .take_while(|line| !(line.starts_with("# -") && line.contains(">8")))
+        .any(|line| !line.trim().is_empty() && !line.starts_with('#'))
+}
+
+/// 将生成的 message 写在 git 的注释说明之前
+fn insert_message(message: &str, existing: &str) -> String {
+    format!("{}\n{}", message.trim_end(), existing)
+}
+
+/// prepare-commit-msg hook 入口
+async fn prepare_commit_msg(
+    cli: &Cli,
+    config: &AppConfig,
+    file: &str,
+    source: Option<&str>,
+) -> Result<()> {
+    if !should_generate(source) {
+        tracing::debug!("Skipping hook for message source {:?}", source);
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(file)?;
+    if has_message_content(&existing) {
+        tracing::debug!("Commit message file already has content, skipping");
+        return Ok(());
+    }
+
+    let colored = config.ui.colored;
+    let repo = GitRepository::open_from_env(Some(&config.file))?;
+    if !repo.has_staged_changes()? {
+        return Ok(());
+    }
+
+    let diff = repo.get_staged_diff()?;
+    let stats = repo.get_diff_stats(&diff)?;
+    let diff = preflight::prepare_diff(&diff, config, colored)?;
+    preflight::check_token_budget(&diff, config, cli.provider.as_deref(), colored)?;
+
+    let provider = create_provider(config, cli.provider.as_deref())?;
+    let context = CommitContext {
+        files_changed: stats.files_changed,
+        insertions: stats.insertions,
+        deletions: stats.deletions,
+        branch_name: repo.get_current_branch()?,
+        custom_prompt: config.commit.custom_prompt.clone(),
+        user_feedback: vec![],
+    };
+
+    eprintln!("gcop-rs: generating commit message...");
+    let message = provider
+        .generate_commit_message(&diff, Some(context), None)
+        .await?;
+
+    fs::write(file, insert_message(&message, &existing))?;
+    Ok(())
+}
+
+/// commit-msg hook 入口：检查消息是否符合规范，不符合时返回错误以阻止提交
+async fn commit_msg(cli: &Cli, config: &AppConfig, file: &str) -> Result<()> {
+    let colored = config.ui.colored;
+    let message = strip_comments(&fs::read_to_string(file)?);
+    let violations = lint_message(&message, &config.convention);
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    ui::error("Commit message does not follow the convention:", colored);
+    for violation in &violations {
+        eprintln!("  - {}", violation);
+    }
+
+    if config.convention.suggest_rewrite {
+        match suggest_rewrite(cli, config, &message, &violations).await {
+            Ok(suggestion) => {
+                eprintln!();
+                eprintln!("{}", ui::info("Suggested message:", colored));
+                eprintln!("{}", suggestion.trim());
+            }
+            Err(e) => eprintln!("gcop-rs: could not get a rewrite suggestion: {}", e),
+        }
+    }
+    eprintln!();
+
+    Err(GcopError::CommitMessageInvalid(violations.len()))
+}
+
+/// 请 provider 给出符合规范的改写
+async fn suggest_rewrite(
+    cli: &Cli,
+    config: &AppConfig,
+    message: &str,
+    violations: &[LintViolation],
+) -> Result<String> {
+    let provider = create_provider(config, cli.provider.as_deref())?;
+    let violations: Vec<String> = violations.iter().map(|v| v.message.clone()).collect();
+    let prompt = build_rewrite_prompt(message, &describe_rules(&config.convention), &violations);
+    provider.complete(&prompt, None).await
+}
+
+/// pre-push 通过 stdin 传入的一条 ref 更新
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PushUpdate {
+    local_ref: String,
+    local_sha: String,
+    remote_ref: String,
+    remote_sha: String,
+}
+
+/// 解析 pre-push 的 stdin：`<local ref> <local sha> <remote ref> <remote sha>`
+fn parse_push_updates(input: &str) -> Vec<PushUpdate> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts.as_slice() {
+                [local_ref, local_sha, remote_ref, remote_sha] => Some(PushUpdate {
+                    local_ref: local_ref.to_string(),
+                    local_sha: local_sha.to_string(),
+                    remote_ref: remote_ref.to_string(),
+                    remote_sha: remote_sha.to_string(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// 全零 sha 表示 ref 不存在（删除分支或远端新建分支）
+fn is_zero_sha(sha: &str) -> bool {
+    sha.chars().all(|c| c == '0')
+}
+
+/// 环境变量是否要求跳过审查（空、"0"、"false" 视为未设置）
+fn is_skip_requested(value: Option<&str>) -> bool {
+    match value {
+        Some(v) => {
+            let v = v.trim();
+            !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false")
+        }
+        None => false,
+    }
+}
+
+/// pre-push hook 入口：审查每个 ref 待推送的 commit
+///
+/// 无法完成审查（如 provider 未配置、网络错误）时只警告不阻止推送；
+/// 发现达到 `review.pre_push_severity` 的问题时返回错误以阻止推送
+async fn pre_push(cli: &Cli, config: &AppConfig, remote: &str) -> Result<()> {
+    if is_skip_requested(std::env::var(SKIP_REVIEW_ENV).ok().as_deref()) {
+        eprintln!(
+            "gcop-rs: {} is set, skipping pre-push review",
+            SKIP_REVIEW_ENV
+        );
+        return Ok(());
+    }
+
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    let updates: Vec<PushUpdate> = parse_push_updates(&input)
+        .into_iter()
+        .filter(|update| !is_zero_sha(&update.local_sha))
+        .collect();
+    if updates.is_empty() {
+        return Ok(());
+    }
+
+    let repo = GitRepository::open_from_env(Some(&config.file))?;
+    let provider = match create_provider(config, cli.provider.as_deref()) {
+        Ok(provider) if cli.no_cache => provider,
+        Ok(provider) => with_cache(provider, config, cli.provider.as_deref())?,
+        Err(e) => {
+            eprintln!("gcop-rs: skipped pre-push review: {}", e);
+            return Ok(());
+        }
+    };
+    let threshold = IssueSeverity::from_config(&config.review.pre_push_severity);
+
+    let mut blocking = 0;
+    for update in &updates {
+        let Some(range) = repo.get_push_range(&update.local_sha, &update.remote_sha, remote)?
+        else {
+            continue;
+        };
+
+        match review_push_range(cli, config, &repo, &provider, update, &range, threshold).await {
+            Ok(count) => blocking += count,
+            Err(e) => eprintln!("gcop-rs: skipped review of {}: {}", update.local_ref, e),
+        }
+    }
+
+    if blocking > 0 {
+        return Err(GcopError::ReviewGateFailed(blocking));
+    }
+    Ok(())
+}
+
+/// 审查一个 ref 的待推送范围，返回达到阈值的问题数
+async fn review_push_range(
+    cli: &Cli,
+    config: &AppConfig,
+    repo: &GitRepository,
+    provider: &Arc<dyn LLMProvider>,
+    update: &PushUpdate,
+    range: &str,
+    threshold: IssueSeverity,
+) -> Result<usize> {
+    let diff = repo.get_range_diff(range)?;
+    if diff.trim().is_empty() {
+        return Ok(0);
+    }
+
+    eprintln!(
+        "gcop-rs: reviewing {} -> {}...",
+        update.local_ref, update.remote_ref
+    );
+    let rules = match repo.workdir() {
+        Some(root) => ReviewRules::load(root)?,
+        None => ReviewRules::default(),
+    };
+    let (result, _) = review::review_diff(
+        provider,
+        &diff,
+        ReviewType::CommitRange(range.to_string()),
+        config,
+        cli.provider.as_deref(),
+        &rules,
+        None,
+    )
+    .await?;
+
+    review::print_text(&result, &format!("Push {}", update.local_ref), config);
+    Ok(review::count_at_or_above(&result, threshold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_should_generate() {
+        assert!(should_generate(None));
+        assert!(should_generate(Some("template")));
+        assert!(!should_generate(Some("message")));
+        assert!(!should_generate(Some("merge")));
+        assert!(!should_generate(Some("squash")));
+        assert!(!should_generate(Some("commit")));
+    }
+
+    #[test]
+    fn test_has_message_content() {
+        assert!(!has_message_content(
+            "\n# Please enter the commit message for your changes.\n#\n"
+        ));
+        assert!(has_message_content("feat: existing\n\n# comment\n"));
+        assert!(!has_message_content(
+            "\n# comment\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n+added\n"
+        ));
+    }
+
+    #[test]
+    fn test_insert_message_keeps_comments() {
+        let existing = "\n# Please enter the commit message\n";
+        assert_eq!(
+            insert_message("feat: add hook\n", existing),
+            "feat: add hook\n\n# Please enter the commit message\n"
+        );
+    }
+
+    #[test]
+    fn test_install_and_uninstall_fresh() {
+        let dir = TempDir::new().unwrap();
+        let hooks = dir.path().join("hooks");
+
+        assert_eq!(
+            install_hook(&hooks, PREPARE_COMMIT_MSG).unwrap(),
+            InstallOutcome::Installed
+        );
+        let path = hooks.join(PREPARE_COMMIT_MSG);
+        assert!(is_gcop_hook(&path));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+
+        assert_eq!(
+            install_hook(&hooks, PREPARE_COMMIT_MSG).unwrap(),
+            InstallOutcome::Updated
+        );
+        assert_eq!(
+            uninstall_hook(&hooks, PREPARE_COMMIT_MSG).unwrap(),
+            UninstallOutcome::Removed(false)
+        );
+        assert!(!path.exists());
+        assert_eq!(
+            uninstall_hook(&hooks, PREPARE_COMMIT_MSG).unwrap(),
+            UninstallOutcome::NotInstalled
+        );
+    }
+
+    #[test]
+    fn test_install_chains_existing_hook() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(PREPARE_COMMIT_MSG);
+        fs::write(&path, "#!/bin/sh\necho husky\n").unwrap();
+
+        let outcome = install_hook(dir.path(), PREPARE_COMMIT_MSG).unwrap();
+        let backup = dir.path().join("prepare-commit-msg.pre-gcop");
+        assert_eq!(outcome, InstallOutcome::Chained(backup.clone()));
+        assert_eq!(
+            fs::read_to_string(&backup).unwrap(),
+            "#!/bin/sh\necho husky\n"
+        );
+        assert!(fs::read_to_string(&path).unwrap().contains("pre-gcop"));
+
+        assert_eq!(
+            uninstall_hook(dir.path(), PREPARE_COMMIT_MSG).unwrap(),
+            UninstallOutcome::Removed(true)
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "#!/bin/sh\necho husky\n"
+        );
+        assert!(!backup.exists());
+    }
+
+    #[test]
+    fn test_hook_script_blocking() {
+        assert!(hook_script(COMMIT_MSG).contains("gcop-rs hook commit-msg \"$@\" </dev/null\n"));
+        assert!(hook_script(PREPARE_COMMIT_MSG).contains("</dev/null || true"));
+        let pre_push = hook_script(PRE_PUSH);
+        assert!(pre_push.contains("input=\"$(cat)\""));
+        assert!(pre_push.contains("printf '%s\\n' \"$input\" | gcop-rs hook pre-push \"$@\"\n"));
+    }
+
+    #[test]
+    fn test_selected_hooks_default() {
+        assert_eq!(
+            selected_hooks(&[], &[PREPARE_COMMIT_MSG]),
+            vec![PREPARE_COMMIT_MSG]
+        );
+        let requested = vec![COMMIT_MSG.to_string()];
+        assert_eq!(
+            selected_hooks(&requested, &[PREPARE_COMMIT_MSG]),
+            vec![COMMIT_MSG]
+        );
+    }
+
+    #[test]
+    fn test_parse_push_updates() {
+        let input = "refs/heads/main 1111111111111111111111111111111111111111 refs/heads/main 0000000000000000000000000000000000000000\n\ngarbage\n";
+        let updates = parse_push_updates(input);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].local_ref, "refs/heads/main");
+        assert!(is_zero_sha(&updates[0].remote_sha));
+        assert!(!is_zero_sha(&updates[0].local_sha));
+    }
+
+    #[test]
+    fn test_is_skip_requested() {
+        assert!(!is_skip_requested(None));
+        assert!(!is_skip_requested(Some("")));
+        assert!(!is_skip_requested(Some("0")));
+        assert!(!is_skip_requested(Some("false")));
+        assert!(is_skip_requested(Some("1")));
+        assert!(is_skip_requested(Some("yes")));
+    }
+
+    #[test]
+    fn test_uninstall_leaves_foreign_hook() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(PREPARE_COMMIT_MSG), "#!/bin/sh\n").unwrap();
+        assert_eq!(
+            uninstall_hook(dir.path(), PREPARE_COMMIT_MSG).unwrap(),
+            UninstallOutcome::Foreign
+        );
+    }
+
+    #[test]
+    fn test_hooks_dir_respects_core_hooks_path() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        assert_eq!(hooks_dir(&repo).unwrap(), repo.commondir().join("hooks"));
+
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", ".githooks")
+            .unwrap();
+        assert_eq!(
+            hooks_dir(&repo).unwrap(),
+            repo.workdir().unwrap().join(".githooks")
+        );
+    }
+}
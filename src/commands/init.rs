@@ -1,10 +1,40 @@
-use crate::config;
+use crate::commands::init_wizard;
+use crate::config::{self, AppConfig};
 use crate::error::{GcopError, Result};
+use crate::llm::provider::credentials::api_key_env_var;
+use crate::llm::provider::models::default_model;
 use crate::ui;
 use std::fs;
+use std::io::IsTerminal;
+
+/// 支持的 provider（同时也是 API 风格）
+pub const PROVIDERS: &[&str] = &["claude", "openai", "ollama"];
+
+/// 一个 provider 的初始化结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderSetup {
+    pub name: String,
+    pub model: String,
+    /// 用户输入的 API key；使用环境变量时为 None
+    pub api_key: Option<String>,
+}
+
+/// 向导或命令行参数得到的配置
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setup {
+    pub default_provider: String,
+    pub providers: Vec<ProviderSetup>,
+}
 
 /// 初始化配置文件
-pub fn run(force: bool, colored: bool) -> Result<()> {
+///
+/// 指定 `provider` 时不进行任何交互，直接写入配置；否则运行交互式向导
+pub async fn run(
+    force: bool,
+    provider: Option<&str>,
+    model: Option<&str>,
+    colored: bool,
+) -> Result<()> {
     // 1. 获取配置目录和文件路径
     let config_dir = config::get_config_dir()
         .ok_or_else(|| GcopError::Config("Failed to determine config directory".to_string()))?;
@@ -23,42 +53,64 @@ pub fn run(force: bool, colored: bool) -> Result<()> {
         return Ok(());
     }
 
-    // 3. 创建配置目录
-    fs::create_dir_all(&config_dir)?;
-    ui::success(
-        &format!("Created config directory: {}", config_dir.display()),
-        colored,
-    );
+    // 3. 确定 provider 和模型
+    let interactive = provider.is_none();
+    let setup = match provider {
+        Some(name) => from_args(name, model)?,
+        None => {
+            if !(std::io::stdin().is_terminal() && std::io::stdout().is_terminal()) {
+                return Err(GcopError::InvalidInput(
+                    "The setup wizard needs a terminal; use --provider (and --model) to initialize non-interactively".to_string(),
+                ));
+            }
+            init_wizard::run(colored).await?
+        }
+    };
+    let content = render_config(&setup)?;
 
-    // 4. 复制示例配置
-    let example_config = include_str!("../../examples/config.toml.example");
-    fs::write(&config_file, example_config)?;
+    // 4. 写入配置文件（仅 Unix 设置 600 权限，配置中可能包含 API key）
+    // 覆盖已有文件时先收紧权限再写入，新文件创建时即为 600
+    fs::create_dir_all(&config_dir)?;
+    #[cfg(unix)]
+    if config_file.exists() {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&config_file, fs::Permissions::from_mode(0o600))?;
+    }
+    config::write_config_file(&config_file, &content)?;
     ui::success(
         &format!("Created config file: {}", config_file.display()),
         colored,
     );
-
-    // 5. 设置文件权限（仅 Unix）
     #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&config_file)?.permissions();
-        perms.set_mode(0o600);
-        fs::set_permissions(&config_file, perms)?;
-        ui::success("Set file permissions: 600", colored);
+    ui::success("Set file permissions: 600", colored);
+
+    // 5. 提示缺少的 API key
+    for provider in &setup.providers {
+        let Some(env_var) = api_key_env_var(&provider.name) else {
+            continue;
+        };
+        let from_env = std::env::var(env_var).is_ok_and(|key| !key.is_empty());
+        if provider.api_key.is_none() && !from_env {
+            ui::warning(
+                &format!(
+                    "No API key for '{}': set {} or add api_key to [llm.providers.{}]",
+                    provider.name, env_var, provider.name
+                ),
+                colored,
+            );
+        }
     }
 
-    // 6. 显示下一步提示
     println!();
     println!("{}", ui::info("Next steps:", colored));
-    println!("  1. Edit the config file:");
-    println!("     gcop-rs config edit");
-    println!();
-    println!("  2. Add your API key to [llm.providers.claude]");
-    println!("     Get key from: https://console.anthropic.com/");
-    println!();
+    println!("  gcop-rs config validate   # test the provider connection");
+    println!("  gcop-rs config edit       # see docs/guide/configuration.md for more options");
 
-    // 7. 询问是否安装 git aliases
+    // 6. 询问是否安装 git aliases（非交互模式跳过）
+    if !interactive {
+        return Ok(());
+    }
+    println!();
     let install_aliases = ui::confirm("Install git aliases (git c, git ac, etc.)?", true)?;
 
     if install_aliases {
@@ -79,8 +131,125 @@ pub fn run(force: bool, colored: bool) -> Result<()> {
         println!("Run 'gcop-rs alias' later if needed.");
     }
 
-    println!();
-    println!("See docs/configuration.md for more options.");
-
     Ok(())
 }
+
+/// 由 `--provider` / `--model` 参数得到配置
+fn from_args(provider: &str, model: Option<&str>) -> Result<Setup> {
+    if !PROVIDERS.contains(&provider) {
+        return Err(GcopError::InvalidInput(format!(
+            "Unknown provider '{}' (expected one of: {})",
+            provider,
+            PROVIDERS.join(", ")
+        )));
+    }
+    Ok(Setup {
+        default_provider: provider.to_string(),
+        providers: vec![ProviderSetup {
+            name: provider.to_string(),
+            model: model.unwrap_or(default_model(provider)).to_string(),
+            api_key: None,
+        }],
+    })
+}
+
+/// 生成最小配置文件内容，并确认它能通过校验
+pub fn render_config(setup: &Setup) -> Result<String> {
+    let mut providers = toml::Table::new();
+    for provider in &setup.providers {
+        let mut table = toml::Table::new();
+        if let Some(key) = &provider.api_key {
+            table.insert("api_key".to_string(), key.clone().into());
+        }
+        table.insert("model".to_string(), provider.model.clone().into());
+        providers.insert(provider.name.clone(), table.into());
+    }
+    let mut llm = toml::Table::new();
+    llm.insert(
+        "default_provider".to_string(),
+        setup.default_provider.clone().into(),
+    );
+    llm.insert("providers".to_string(), providers.into());
+    let mut root = toml::Table::new();
+    root.insert("llm".to_string(), llm.into());
+
+    let body = toml::to_string(&root)
+        .map_err(|e| GcopError::Other(format!("Failed to serialize config: {}", e)))?;
+    let content = format!(
+        "# gcop-rs configuration, created by `gcop-rs init`\n\
+         # All options: https://github.com/AptS-1547/gcop-rs/blob/main/docs/guide/configuration.md\n\n{}",
+        body
+    );
+
+    toml::from_str::<AppConfig>(&content)
+        .map_err(|e| GcopError::Config(format!("Generated config is invalid: {}", e.message())))?;
+    if let Some(issue) = config::check::check_document(&content).first() {
+        return Err(GcopError::Config(format!(
+            "Generated config is invalid: {}",
+            issue
+        )));
+    }
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_from_args() {
+        let setup = from_args("openai", None).unwrap();
+        assert_eq!(setup.default_provider, "openai");
+        assert_eq!(setup.providers[0].model, default_model("openai"));
+
+        let setup = from_args("ollama", Some("qwen2.5-coder:7b")).unwrap();
+        assert_eq!(setup.providers[0].model, "qwen2.5-coder:7b");
+
+        let err = from_args("gemini", None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expected one of: claude, openai, ollama")
+        );
+    }
+
+    #[test]
+    fn test_render_config() {
+        let setup = Setup {
+            default_provider: "claude".to_string(),
+            providers: vec![
+                ProviderSetup {
+                    name: "claude".to_string(),
+                    model: "claude-sonnet-4-5-20250929".to_string(),
+                    api_key: Some("sk-ant-test".to_string()),
+                },
+                ProviderSetup {
+                    name: "ollama".to_string(),
+                    model: "llama3.1:8b".to_string(),
+                    api_key: None,
+                },
+            ],
+        };
+        let content = render_config(&setup).unwrap();
+        let body = content
+            .split("\n\n")
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        assert_eq!(
+            body,
+            "[llm]\n\
+             default_provider = \"claude\"\n\
+             \n\
+             [llm.providers.claude]\n\
+             api_key = \"sk-ant-test\"\n\
+             model = \"claude-sonnet-4-5-20250929\"\n\
+             \n\
+             [llm.providers.ollama]\n\
+             model = \"llama3.1:8b\"\n"
+        );
+
+        let config: AppConfig = toml::from_str(&content).unwrap();
+        assert_eq!(config.llm.providers.len(), 2);
+    }
+}
@@ -0,0 +1,244 @@
+//! `init` 交互式向导
+//!
+//! 检测已有的 `ANTHROPIC_API_KEY` / `OPENAI_API_KEY` 和本地运行的 Ollama，
+//! 让用户选择 provider，从模型列表中选择模型，并发送一次测试请求确认配置可用。
+
+use std::time::Duration;
+
+use dialoguer::{Input, MultiSelect, Password, Select};
+use reqwest::Client;
+
+use crate::commands::init::{PROVIDERS, ProviderSetup, Setup};
+use crate::config::{AppConfig, NetworkConfig, ProviderConfig};
+use crate::error::{GcopError, Result};
+use crate::llm::provider::create_http_client;
+use crate::llm::provider::create_provider;
+use crate::llm::provider::credentials::api_key_env_var;
+use crate::llm::provider::models::{default_model, list_models};
+use crate::ui;
+
+/// 检测本地 Ollama 的超时时间
+const OLLAMA_DETECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 测试请求的 prompt
+const TEST_PROMPT: &str = "Reply with the single word OK.";
+
+/// 运行向导，返回用户选择的配置
+pub async fn run(colored: bool) -> Result<Setup> {
+    let client = create_http_client(&NetworkConfig::default())?;
+
+    // 1. 检测已有的 API key 和本地 Ollama
+    let ollama_models = detect_ollama().await;
+    let detected: Vec<bool> = PROVIDERS
+        .iter()
+        .map(|name| match api_key_env_var(name) {
+            Some(var) => env_key(var).is_some(),
+            None => ollama_models.is_some(),
+        })
+        .collect();
+    for (name, found) in PROVIDERS.iter().zip(&detected) {
+        if !found {
+            continue;
+        }
+        match api_key_env_var(name) {
+            Some(var) => ui::success(&format!("Found {} in the environment", var), colored),
+            None => ui::success("Found a running Ollama at localhost:11434", colored),
+        }
+    }
+
+    // 2. 选择 provider（默认勾选检测到的，都没有时勾选 Claude）
+    let defaults = if detected.iter().any(|found| *found) {
+        detected
+    } else {
+        PROVIDERS.iter().map(|name| *name == "claude").collect()
+    };
+    let labels: Vec<&str> = PROVIDERS.iter().map(|name| provider_label(name)).collect();
+    let selected = MultiSelect::new()
+        .with_prompt("Which providers do you want to configure? (space to toggle)")
+        .items(&labels)
+        .defaults(&defaults)
+        .interact()?;
+    if selected.is_empty() {
+        return Err(GcopError::InvalidInput("No provider selected".to_string()));
+    }
+
+    // 3. 逐个配置
+    let mut providers = Vec::new();
+    for index in selected {
+        let name = PROVIDERS[index];
+        println!();
+        println!(
+            "{}",
+            ui::info(&format!("Configuring {}", provider_label(name)), colored)
+        );
+        if let Some(provider) = setup_provider(&client, name, &ollama_models, colored).await? {
+            providers.push(provider);
+        }
+    }
+    if providers.is_empty() {
+        return Err(GcopError::InvalidInput(
+            "No provider was configured".to_string(),
+        ));
+    }
+
+    // 4. 选择默认 provider
+    let default_provider = if providers.len() == 1 {
+        providers[0].name.clone()
+    } else {
+        println!();
+        let names: Vec<&str> = providers.iter().map(|p| p.name.as_str()).collect();
+        let index = Select::new()
+            .with_prompt("Default provider")
+            .items(&names)
+            .default(0)
+            .interact()?;
+        names[index].to_string()
+    };
+
+    println!();
+    Ok(Setup {
+        default_provider,
+        providers,
+    })
+}
+
+/// 配置单个 provider；测试请求失败且用户选择不保留时返回 None
+async fn setup_provider(
+    client: &Client,
+    name: &str,
+    ollama_models: &Option<Vec<String>>,
+    colored: bool,
+) -> Result<Option<ProviderSetup>> {
+    // API key：优先使用环境变量，否则让用户输入（可留空稍后设置）
+    let (api_key, request_key) = match api_key_env_var(name) {
+        Some(var) => match env_key(var) {
+            Some(key) => {
+                println!("Using {} from the environment", var);
+                (None, Some(key))
+            }
+            None => {
+                let key = Password::new()
+                    .with_prompt(format!("API key (leave empty to set {} later)", var))
+                    .allow_empty_password(true)
+                    .interact()?;
+                let key = key.trim().to_string();
+                if key.is_empty() {
+                    (None, None)
+                } else {
+                    (Some(key.clone()), Some(key))
+                }
+            }
+        },
+        None => (None, None),
+    };
+    let can_connect = request_key.is_some() || name == "ollama";
+
+    // 模型：能获取列表时从列表中选择，否则手动输入
+    let models = match (name, ollama_models) {
+        ("ollama", Some(models)) => Ok(models.clone()),
+        _ if can_connect => {
+            let spinner = ui::Spinner::new("Fetching available models...");
+            let models = list_models(client, name, None, request_key.as_deref()).await;
+            spinner.finish_and_clear();
+            models
+        }
+        _ => Ok(Vec::new()),
+    };
+    let model = match models {
+        Ok(models) if !models.is_empty() => {
+            let default = models
+                .iter()
+                .position(|model| model == default_model(name))
+                .unwrap_or(0);
+            let index = Select::new()
+                .with_prompt("Model")
+                .items(&models)
+                .default(default)
+                .max_length(12)
+                .interact()?;
+            models[index].clone()
+        }
+        result => {
+            if let Err(e) = result {
+                ui::warning(&format!("Could not list models: {}", e), colored);
+            }
+            Input::new()
+                .with_prompt("Model")
+                .default(default_model(name).to_string())
+                .interact_text()?
+        }
+    };
+
+    let setup = ProviderSetup {
+        name: name.to_string(),
+        model,
+        api_key,
+    };
+
+    // 测试请求：没有 key 时跳过
+    if !can_connect {
+        return Ok(Some(setup));
+    }
+    match test_request(&setup, request_key).await {
+        Ok(()) => {
+            ui::success(&format!("{} responded", setup.model), colored);
+            Ok(Some(setup))
+        }
+        Err(e) => {
+            ui::warning(&format!("Test request failed: {}", e), colored);
+            if ui::confirm("Keep this provider anyway?", true)? {
+                Ok(Some(setup))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// 用选定的模型发送一次简短请求
+async fn test_request(setup: &ProviderSetup, api_key: Option<String>) -> Result<()> {
+    let mut config = AppConfig::default();
+    // 测试请求不记入用量日志
+    config.usage.enabled = false;
+    config.llm.default_provider = setup.name.clone();
+    config.llm.providers.insert(
+        setup.name.clone(),
+        ProviderConfig {
+            api_style: None,
+            endpoint: None,
+            api_key,
+            api_key_file: None,
+            api_key_cmd: None,
+            model: setup.model.clone(),
+            max_tokens: Some(16),
+            temperature: None,
+            extra: Default::default(),
+        },
+    );
+    let provider = create_provider(&config, None)?;
+    let spinner = ui::Spinner::new("Sending a test request...");
+    let result = provider.complete(TEST_PROMPT, Some(&spinner)).await;
+    spinner.finish_and_clear();
+    result.map(|_| ())
+}
+
+/// 本地 Ollama 在运行时返回其模型列表
+async fn detect_ollama() -> Option<Vec<String>> {
+    let client = Client::builder()
+        .timeout(OLLAMA_DETECT_TIMEOUT)
+        .build()
+        .ok()?;
+    list_models(&client, "ollama", None, None).await.ok()
+}
+
+fn env_key(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|key| !key.trim().is_empty())
+}
+
+fn provider_label(name: &str) -> &'static str {
+    match name {
+        "claude" => "Claude (Anthropic)",
+        "openai" => "OpenAI",
+        _ => "Ollama (local)",
+    }
+}
@@ -1,7 +1,20 @@
 pub mod alias;
+pub mod cache;
 pub mod commit;
 pub mod commit_state_machine;
 pub mod config;
+pub mod config_keys;
+pub mod config_show;
+pub mod hook;
 pub mod init;
+pub mod init_wizard;
+pub mod preflight;
 pub mod review;
+pub mod review_baseline;
+pub mod review_files;
+pub mod review_fixes;
+pub mod review_format;
+pub mod review_rules;
+pub mod review_triage;
 pub mod stats;
+pub mod usage;
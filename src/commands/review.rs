@@ -1,255 +1,607 @@
+use std::io::IsTerminal;
+use std::sync::Arc;
+
 use crate::cli::{Cli, ReviewTarget};
+use crate::commands::preflight;
+use crate::commands::review_baseline::{self, BASELINE_PATH, Baseline};
+use crate::commands::review_files::{self, FileBatch};
+use crate::commands::review_fixes;
+use crate::commands::review_format::{ReviewFormatter, TextFormatter, formatter_for};
+use crate::commands::review_rules::ReviewRules;
+use crate::commands::review_triage::{self, TriageOutcome};
 use crate::config::AppConfig;
 use crate::error::{GcopError, Result};
+use crate::git::diff::{LineIndex, annotate_line_numbers, split_file_diffs};
 use crate::git::{GitOperations, repository::GitRepository};
-use crate::llm::{IssueSeverity, ReviewResult, ReviewType, provider::create_provider};
+use crate::llm::prompt;
+use crate::llm::provider::{create_provider, with_cache};
+use crate::llm::{IssueSeverity, LLMProvider, ReviewIssue, ReviewResult, ReviewType};
 use crate::ui;
 
+/// review 命令的输出与门禁选项
+#[derive(Debug, Clone, Copy)]
+pub struct ReviewOptions<'a> {
+    /// 输出格式（`--format`）
+    pub format: &'a str,
+    /// 存在该严重性及以上的问题时返回错误（用于 CI 门禁）
+    pub fail_on: Option<&'a str>,
+    /// 输出结果后进入逐个问题的分诊
+    pub interactive: bool,
+    /// 应用全部能干净应用的修复补丁
+    pub apply_fixes: bool,
+    /// 把本次结果写入审查基线（分诊时只写入忽略和误报的问题）
+    pub update_baseline: bool,
+}
+
 /// 执行 review 命令
-pub async fn run(cli: &Cli, config: &AppConfig, target: &ReviewTarget, format: &str) -> Result<()> {
+pub async fn run(
+    cli: &Cli,
+    config: &AppConfig,
+    target: &ReviewTarget,
+    options: &ReviewOptions<'_>,
+) -> Result<()> {
     let colored = config.ui.colored;
+    let ReviewOptions {
+        format,
+        fail_on,
+        interactive,
+        apply_fixes,
+        update_baseline,
+    } = *options;
+    if interactive {
+        if format != "text" {
+            return Err(GcopError::InvalidInput(
+                "--interactive only works with text output".to_string(),
+            ));
+        }
+        if !(std::io::stdin().is_terminal() && std::io::stdout().is_terminal()) {
+            return Err(GcopError::InvalidInput(
+                "--interactive requires a terminal".to_string(),
+            ));
+        }
+    }
+    let formatter = formatter_for(format, config);
+    // 机器可读格式的 stdout 只包含报告本身，不输出进度
+    let machine_output = formatter.is_machine_readable();
+    let step = |n: &str, msg: &str| {
+        if !machine_output {
+            ui::step(n, msg, colored);
+        }
+    };
     let repo = GitRepository::open(Some(&config.file))?;
-    let provider = create_provider(config, cli.provider.as_deref())?;
+    let mut provider = create_provider(config, cli.provider.as_deref())?;
+
+    // 已提交内容的审查结果是确定的，默认走本地缓存
+    let immutable_target = matches!(
+        target,
+        ReviewTarget::Commit { .. } | ReviewTarget::Range { .. } | ReviewTarget::Branch { .. }
+    );
+    if immutable_target && !cli.no_cache {
+        provider = with_cache(provider, config, cli.provider.as_deref())?;
+    }
 
     // 根据目标类型路由
-    let (diff, description) = match target {
+    let (batches, description, review_type) = match target {
         ReviewTarget::Changes => {
-            ui::step("1/3", "Analyzing uncommitted changes...", colored);
+            step("1/3", "Analyzing unstaged changes...");
             let diff = repo.get_uncommitted_diff()?;
             if diff.trim().is_empty() {
-                ui::error("No uncommitted changes found.", colored);
+                // 改动已全部 git add 时提示改用 staged
+                let hint = if repo.has_staged_changes()? {
+                    " Your changes are staged, use 'gcop-rs review staged'."
+                } else {
+                    ""
+                };
+                ui::error(&format!("No unstaged changes found.{}", hint), colored);
                 return Err(GcopError::InvalidInput(
-                    "No uncommitted changes to review".to_string(),
+                    "No unstaged changes to review".to_string(),
                 ));
             }
-            (diff, "Uncommitted changes".to_string())
+            (
+                single(diff),
+                "Unstaged changes".to_string(),
+                ReviewType::UncommittedChanges,
+            )
+        }
+        ReviewTarget::Staged => {
+            step("1/3", "Analyzing staged changes...");
+            let diff = repo.get_staged_diff()?;
+            if diff.trim().is_empty() {
+                ui::error("No staged changes found. Use 'git add' first.", colored);
+                return Err(GcopError::NoStagedChanges);
+            }
+            (
+                single(diff),
+                "Staged changes".to_string(),
+                ReviewType::StagedChanges,
+            )
+        }
+        ReviewTarget::Worktree => {
+            step("1/3", "Analyzing all working tree changes...");
+            let diff = repo.get_worktree_diff()?;
+            if diff.trim().is_empty() {
+                ui::error("Working tree is clean.", colored);
+                return Err(GcopError::InvalidInput(
+                    "No working tree changes to review".to_string(),
+                ));
+            }
+            (
+                single(diff),
+                "Working tree changes".to_string(),
+                ReviewType::WorkingTree,
+            )
         }
         ReviewTarget::Commit { hash } => {
-            ui::step("1/3", &format!("Analyzing commit {}...", hash), colored);
+            step("1/3", &format!("Analyzing commit {}...", hash));
             let diff = repo.get_commit_diff(hash)?;
-            (diff, format!("Commit {}", hash))
+            (
+                single(diff),
+                format!("Commit {}", hash),
+                ReviewType::SingleCommit(hash.clone()),
+            )
         }
         ReviewTarget::Range { range } => {
-            ui::step("1/3", &format!("Analyzing range {}...", range), colored);
+            step("1/3", &format!("Analyzing range {}...", range));
             let diff = repo.get_range_diff(range)?;
-            (diff, format!("Commit range {}", range))
+            (
+                single(diff),
+                format!("Commit range {}", range),
+                ReviewType::CommitRange(range.clone()),
+            )
+        }
+        ReviewTarget::Branch { base } => {
+            let base = match base {
+                Some(base) => base.clone(),
+                None => repo.get_branch_base()?,
+            };
+            step("1/3", &format!("Analyzing branch against {}...", base));
+            let range = branch_range(&base);
+            let diff = repo.get_range_diff(&range)?;
+            if diff.trim().is_empty() {
+                return Err(GcopError::InvalidInput(format!(
+                    "No changes between {} and HEAD",
+                    base
+                )));
+            }
+            (
+                single(diff),
+                format!("Branch changes since {}", base),
+                ReviewType::CommitRange(range),
+            )
         }
         ReviewTarget::File { path } => {
-            ui::step("1/3", &format!("Analyzing file {}...", path), colored);
-            let content = repo.get_file_content(path)?;
-            // 文件审查需要特殊处理，将内容包装成 diff 格式
-            let diff = format!("--- {}\n+++ {}\n{}", path, path, content);
-            (diff, format!("File {}", path))
+            step("1/3", &format!("Analyzing {}...", path));
+            let target = std::path::Path::new(path);
+            if target.is_file()
+                && preflight::is_path_excluded(
+                    &review_files::repo_relative(target, repo.workdir()),
+                    config,
+                )?
+            {
+                return Err(GcopError::InvalidInput(format!(
+                    "{} matches security.exclude_paths and will not be sent to the LLM",
+                    path
+                )));
+            }
+            let mut selection = review_files::collect_files(path, &repo, repo.workdir(), config)?;
+            let limit = preflight::request_token_limit(config, cli.provider.as_deref());
+            let batches = review_files::batch_files(selection.files, limit, &mut selection.skipped);
+
+            for (skipped, reason) in &selection.skipped {
+                warn(
+                    &format!("Skipped {}: {}", skipped, reason),
+                    machine_output,
+                    colored,
+                );
+            }
+            let file_count: usize = batches.iter().map(|b| b.paths.len()).sum();
+            if batches.is_empty() {
+                return Err(GcopError::InvalidInput(format!(
+                    "No reviewable files found in {}",
+                    path
+                )));
+            }
+            if batches.len() > 1 {
+                step(
+                    "1/3",
+                    &format!(
+                        "Splitting {} files into {} requests...",
+                        file_count,
+                        batches.len()
+                    ),
+                );
+            }
+
+            let description = if file_count == 1 {
+                format!("File {}", path)
+            } else {
+                format!("Files {}", path)
+            };
+            (batches, description, ReviewType::FileOrDir(path.clone()))
         }
     };
 
-    // 调用 LLM 进行审查
-    let spinner = ui::Spinner::new("Reviewing code with AI...");
-
-    let review_type = match target {
-        ReviewTarget::Changes => ReviewType::UncommittedChanges,
-        ReviewTarget::Commit { hash } => ReviewType::SingleCommit(hash.clone()),
-        ReviewTarget::Range { range } => ReviewType::CommitRange(range.clone()),
-        ReviewTarget::File { path } => ReviewType::FileOrDir(path.clone()),
+    // 逐批发送
+    let rules = match repo.workdir() {
+        Some(root) => ReviewRules::load(root)?,
+        None => ReviewRules::default(),
     };
-
-    let result = provider
-        .review_code(
-            &diff,
-            review_type,
-            config.review.custom_prompt.as_deref(),
-            Some(&spinner),
+    let total = batches.len();
+    let mut results = Vec::with_capacity(total);
+    // 预检后实际发送的 diff，分诊和基线都基于它
+    let mut diff = String::new();
+    for (i, batch) in batches.iter().enumerate() {
+        let message = if total > 1 {
+            format!("Reviewing code with AI ({}/{})...", i + 1, total)
+        } else {
+            "Reviewing code with AI...".to_string()
+        };
+        let (result, sent) = review_diff(
+            &provider,
+            &batch.diff,
+            review_type.clone(),
+            config,
+            cli.provider.as_deref(),
+            &rules,
+            Some(&message),
         )
         .await?;
+        results.push(result);
+        diff.push_str(&sent);
+    }
+    let mut result = merge_results(results);
+    let dropped = review_fixes::validate_patches(&repo, &mut result.issues);
+    if dropped > 0 {
+        tracing::debug!("Dropped {} suggested fix(es) that do not apply", dropped);
+    }
 
-    spinner.finish_and_clear();
+    // 过滤基线中的已知问题
+    let baseline_path = repo.workdir().map(|root| root.join(BASELINE_PATH));
+    let mut baseline = match &baseline_path {
+        Some(path) => Baseline::load(path)?,
+        None => Baseline::default(),
+    };
+    let suppressed = review_baseline::suppress(&mut result.issues, &baseline, &diff);
 
     // 格式化输出
-    ui::step("3/3", "Formatting results...", colored);
-    println!();
-
-    match format {
-        "json" => print_json(&result)?,
-        "markdown" => print_markdown(&result, &description, colored),
-        _ => print_text(&result, &description, config),
+    if !machine_output {
+        ui::step("3/3", "Formatting results...", colored);
+        println!();
     }
 
-    Ok(())
-}
+    print!("{}", formatter.render(&result, &description)?);
+    if suppressed > 0 {
+        note(
+            &format!(
+                "{} known issue(s) suppressed by {}",
+                suppressed, BASELINE_PATH
+            ),
+            machine_output,
+            colored,
+        );
+    }
 
-/// 以文本格式输出审查结果
-fn print_text(result: &ReviewResult, description: &str, config: &AppConfig) {
-    let colored = config.ui.colored;
+    let mut triaged = None;
+    if interactive && !result.issues.is_empty() {
+        let records = review_triage::run(&repo, &provider, &result, &diff, config).await?;
+        println!();
+        print!("{}", review_triage::render_report(&records, colored));
+        triaged = Some(records);
+    }
 
-    println!("{}", ui::info(&format!("Review: {}", description), colored));
-    println!();
+    if update_baseline && let Some(path) = &baseline_path {
+        // 分诊过的只记录忽略和误报的问题，否则记录全部结果
+        let known: Vec<&ReviewIssue> = match &triaged {
+            Some(records) => records
+                .iter()
+                .filter(|r| {
+                    matches!(
+                        r.outcome,
+                        TriageOutcome::Dismissed | TriageOutcome::FalsePositive
+                    )
+                })
+                .map(|r| &r.issue)
+                .collect(),
+            None => result.issues.iter().collect(),
+        };
+        let added = known
+            .into_iter()
+            .filter(|issue| baseline.insert(issue, &diff))
+            .count();
+        if added > 0 {
+            baseline.save(path)?;
+        }
+        note(
+            &format!("Added {} issue(s) to {}", added, BASELINE_PATH),
+            machine_output,
+            colored,
+        );
+    }
 
-    // 输出摘要
-    println!("📝 Summary:");
-    println!("{}", result.summary);
-    println!();
+    if apply_fixes {
+        apply_all_fixes(&repo, &result, machine_output, colored);
+    }
 
-    // 输出问题
-    if !result.issues.is_empty() {
-        println!("🔍 Issues found:");
-        println!();
+    if let Some(threshold) = fail_on {
+        let count = count_at_or_above(&result, IssueSeverity::from_config(threshold));
+        if count > 0 {
+            return Err(GcopError::ReviewThresholdExceeded {
+                count,
+                threshold: threshold.to_string(),
+            });
+        }
+    }
 
-        for (i, issue) in result.issues.iter().enumerate() {
-            // 根据配置过滤严重性
-            let min_severity = match config.review.min_severity.as_str() {
-                "critical" => IssueSeverity::Critical,
-                "warning" => IssueSeverity::Warning,
-                _ => IssueSeverity::Info,
-            };
+    Ok(())
+}
 
-            // 跳过低于最小严重性的问题
-            let issue_level = match issue.severity {
-                IssueSeverity::Critical => 0,
-                IssueSeverity::Warning => 1,
-                IssueSeverity::Info => 2,
-            };
+/// 应用全部修复补丁并逐个报告结果；机器可读格式下输出到 stderr
+fn apply_all_fixes(
+    repo: &GitRepository,
+    result: &ReviewResult,
+    machine_output: bool,
+    colored: bool,
+) {
+    let outcomes = review_fixes::apply_all(repo, &result.issues);
+    if outcomes.is_empty() {
+        warn("No suggested fixes to apply", machine_output, colored);
+        return;
+    }
+    for (number, outcome) in outcomes {
+        match outcome {
+            Ok(()) if machine_output => eprintln!("✓ Applied fix for issue {}", number),
+            Ok(()) => ui::success(&format!("Applied fix for issue {}", number), colored),
+            Err(e) => warn(
+                &format!("Skipped fix for issue {}: {}", number, e),
+                machine_output,
+                colored,
+            ),
+        }
+    }
+}
 
-            let min_level = match min_severity {
-                IssueSeverity::Critical => 0,
-                IssueSeverity::Warning => 1,
-                IssueSeverity::Info => 2,
-            };
+/// 预检并审查一段 diff
+///
+/// 先剔除排除路径并扫描密钥，标注新文件行号后检查 token 预算；prompt 中加入与
+/// diff 中文件相关的仓库规则。返回后按 diff 校正问题的文件和行号。
+/// `spinner_message` 为 None 时不显示 spinner。
+///
+/// # Returns
+/// (审查结果, 实际发送的 diff)；后续再发给 provider 或写入基线时应使用后者，
+/// 以免泄露被剔除或脱敏的内容
+pub async fn review_diff(
+    provider: &Arc<dyn LLMProvider>,
+    diff: &str,
+    review_type: ReviewType,
+    config: &AppConfig,
+    provider_name: Option<&str>,
+    rules: &ReviewRules,
+    spinner_message: Option<&str>,
+) -> Result<(ReviewResult, String)> {
+    let colored = config.ui.colored;
+    let diff = preflight::prepare_diff(diff, config, colored)?;
+    let annotated = annotate_line_numbers(&diff);
+    preflight::check_token_budget(&annotated, config, provider_name, colored)?;
+
+    // 只加入与本次 diff 中文件相关的仓库规则
+    let custom_prompt = config.review.custom_prompt.as_deref();
+    let template = if rules.is_empty() {
+        None
+    } else {
+        let files: Vec<String> = split_file_diffs(&diff)
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        rules
+            .render_for(&files)
+            .map(|rules| prompt::with_project_rules(custom_prompt, &rules))
+    };
 
-            if issue_level > min_level {
-                continue;
-            }
+    let spinner = spinner_message.map(ui::Spinner::new);
+    let mut result = provider
+        .review_code(
+            &annotated,
+            review_type,
+            template.as_deref().or(custom_prompt),
+            spinner.as_ref(),
+        )
+        .await?;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
 
-            // 格式化严重性标签
-            let severity_label = match issue.severity {
-                IssueSeverity::Critical => {
-                    if colored {
-                        "CRITICAL".to_string()
-                    } else {
-                        "[CRITICAL]".to_string()
-                    }
-                }
-                IssueSeverity::Warning => {
-                    if colored {
-                        "WARNING".to_string()
-                    } else {
-                        "[WARNING]".to_string()
-                    }
-                }
-                IssueSeverity::Info => {
-                    if colored {
-                        "INFO".to_string()
-                    } else {
-                        "[INFO]".to_string()
-                    }
-                }
-            };
+    validate_locations(&mut result.issues, &LineIndex::from_diff(&diff));
+    Ok((result, diff))
+}
 
-            // 输出问题
-            print!("  {}. ", i + 1);
+/// 按 diff 校正问题位置
+///
+/// 文件不在 diff 中时丢弃位置；行号不在 diff 中时吸附到附近的新增行，太远则只保留文件。
+/// diff 只涉及一个文件时，未标注文件的问题归到该文件。
+fn validate_locations(issues: &mut [ReviewIssue], index: &LineIndex) {
+    if index.is_empty() {
+        return;
+    }
+    for issue in issues {
+        let path = match issue.file.as_deref() {
+            Some(file) if !file.trim().is_empty() => index.resolve_path(file),
+            _ => index.single_file(),
+        };
+        let Some(path) = path else {
+            issue.file = None;
+            issue.line = None;
+            continue;
+        };
+        issue.line = issue.line.and_then(|line| index.snap_line(path, line));
+        issue.file = Some(path.to_string());
+    }
+}
 
-            if colored {
-                use colored::Colorize;
-                match issue.severity {
-                    IssueSeverity::Critical => print!("{}", severity_label.red().bold()),
-                    IssueSeverity::Warning => print!("{}", severity_label.yellow().bold()),
-                    IssueSeverity::Info => print!("{}", severity_label.blue().bold()),
-                }
-            } else {
-                print!("{}", severity_label);
-            }
+/// 单个 diff 作为一次请求
+fn single(diff: String) -> Vec<FileBatch> {
+    vec![FileBatch {
+        paths: Vec::new(),
+        diff,
+    }]
+}
 
-            println!(" {}", issue.description);
+/// 输出警告；机器可读格式下写到 stderr，避免混入报告
+fn warn(message: &str, machine_output: bool, colored: bool) {
+    if machine_output {
+        eprintln!("⚠ {}", message);
+    } else {
+        ui::warning(message, colored);
+    }
+}
 
-            // 输出位置信息
-            if let Some(file) = &issue.file {
-                if let Some(line) = issue.line {
-                    println!("     Location: {}:{}", file, line);
-                } else {
-                    println!("     Location: {}", file);
-                }
-            }
-            println!();
-        }
+/// 输出提示信息；机器可读格式下写到 stderr，避免混入报告
+fn note(message: &str, machine_output: bool, colored: bool) {
+    if machine_output {
+        eprintln!("ℹ {}", message);
     } else {
-        println!("✨ No issues found!");
-        println!();
+        println!("{}", ui::info(message, colored));
     }
+}
 
-    // 输出建议
-    if !result.suggestions.is_empty() {
-        println!("💡 Suggestions:");
-        println!();
-        for suggestion in &result.suggestions {
-            println!("  • {}", suggestion);
+/// 合并分批审查的结果
+fn merge_results(results: Vec<ReviewResult>) -> ReviewResult {
+    let mut merged = ReviewResult {
+        summary: String::new(),
+        issues: Vec::new(),
+        suggestions: Vec::new(),
+    };
+    for result in results {
+        if !result.summary.trim().is_empty() {
+            if !merged.summary.is_empty() {
+                merged.summary.push_str("\n\n");
+            }
+            merged.summary.push_str(result.summary.trim());
+        }
+        merged.issues.extend(result.issues);
+        for suggestion in result.suggestions {
+            if !merged.suggestions.contains(&suggestion) {
+                merged.suggestions.push(suggestion);
+            }
         }
-        println!();
     }
+    merged
 }
 
-/// 以 JSON 格式输出审查结果
-fn print_json(result: &ReviewResult) -> Result<()> {
-    let json = serde_json::to_string_pretty(result)?;
-    println!("{}", json);
-    Ok(())
+/// 当前分支相对 base 的范围（三点语法，从 merge base 开始）
+fn branch_range(base: &str) -> String {
+    format!("{}...HEAD", base)
 }
 
-/// 以 Markdown 格式输出审查结果
-fn print_markdown(result: &ReviewResult, description: &str, _colored: bool) {
-    println!("# Code Review: {}", description);
-    println!();
+/// 统计达到阈值的问题数（不受 `min_severity` 显示过滤影响）
+pub fn count_at_or_above(result: &ReviewResult, threshold: IssueSeverity) -> usize {
+    result
+        .issues
+        .iter()
+        .filter(|issue| issue.severity.meets(threshold))
+        .count()
+}
 
-    // 摘要
-    println!("## Summary");
-    println!();
-    println!("{}", result.summary);
-    println!();
+/// 以文本格式输出审查结果
+pub fn print_text(result: &ReviewResult, description: &str, config: &AppConfig) {
+    // 文本渲染不会失败
+    if let Ok(text) = TextFormatter::from_config(config).render(result, description) {
+        print!("{}", text);
+    }
+}
 
-    // 问题
-    if !result.issues.is_empty() {
-        println!("## Issues");
-        println!();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::ReviewIssue;
+    use pretty_assertions::assert_eq;
+
+    fn result_with(severities: &[IssueSeverity]) -> ReviewResult {
+        ReviewResult {
+            summary: String::new(),
+            issues: severities
+                .iter()
+                .map(|severity| ReviewIssue {
+                    severity: *severity,
+                    description: "issue".to_string(),
+                    file: None,
+                    line: None,
+                    patch: None,
+                })
+                .collect(),
+            suggestions: vec![],
+        }
+    }
 
-        for issue in &result.issues {
-            let severity_emoji = match issue.severity {
-                IssueSeverity::Critical => "🔴",
-                IssueSeverity::Warning => "🟡",
-                IssueSeverity::Info => "🔵",
-            };
+    #[test]
+    fn test_merge_results() {
+        let mut first = result_with(&[IssueSeverity::Warning]);
+        first.summary = "Batch one".to_string();
+        first.suggestions = vec!["Add tests".to_string()];
+        let mut second = result_with(&[IssueSeverity::Info, IssueSeverity::Critical]);
+        second.summary = "Batch two".to_string();
+        second.suggestions = vec!["Add tests".to_string(), "Split module".to_string()];
+
+        let merged = merge_results(vec![first, second]);
+        assert_eq!(merged.summary, "Batch one\n\nBatch two");
+        assert_eq!(merged.issues.len(), 3);
+        assert_eq!(merged.suggestions, vec!["Add tests", "Split module"]);
+    }
 
-            let severity_text = match issue.severity {
-                IssueSeverity::Critical => "**CRITICAL**",
-                IssueSeverity::Warning => "**WARNING**",
-                IssueSeverity::Info => "**INFO**",
-            };
+    fn issue_at(file: Option<&str>, line: Option<usize>) -> ReviewIssue {
+        ReviewIssue {
+            severity: IssueSeverity::Warning,
+            description: "issue".to_string(),
+            file: file.map(String::from),
+            line,
+            patch: None,
+        }
+    }
 
-            println!("### {} {}", severity_emoji, severity_text);
-            println!();
-            println!("{}", issue.description);
-            println!();
+    #[test]
+    fn test_validate_locations() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1,2 +1,3 @@\n one\n+two\n three\ndiff --git a/src/b.rs b/src/b.rs\n--- a/src/b.rs\n+++ b/src/b.rs\n@@ -50,1 +50,2 @@\n keep\n+added\n";
+        let index = LineIndex::from_diff(diff);
+        let mut issues = vec![
+            issue_at(Some("src/a.rs"), Some(2)),
+            issue_at(Some("b.rs"), Some(55)),
+            issue_at(Some("src/b.rs"), Some(400)),
+            issue_at(Some("src/unknown.rs"), Some(3)),
+            issue_at(None, Some(1)),
+        ];
+        validate_locations(&mut issues, &index);
+
+        let locations: Vec<_> = issues.iter().map(|i| (i.file.as_deref(), i.line)).collect();
+        assert_eq!(
+            locations,
+            vec![
+                (Some("src/a.rs"), Some(2)),
+                (Some("src/b.rs"), Some(51)),
+                (Some("src/b.rs"), None),
+                (None, None),
+                (None, None),
+            ]
+        );
+    }
 
-            if let Some(file) = &issue.file {
-                if let Some(line) = issue.line {
-                    println!("**Location:** `{}:{}`", file, line);
-                } else {
-                    println!("**Location:** `{}`", file);
-                }
-                println!();
-            }
-        }
-    } else {
-        println!("## Issues");
-        println!();
-        println!("✨ No issues found!");
-        println!();
+    #[test]
+    fn test_validate_locations_single_file() {
+        let index = LineIndex::from_diff("--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1 @@\n+fn a() {}\n");
+        let mut issues = vec![issue_at(None, Some(1))];
+        validate_locations(&mut issues, &index);
+        assert_eq!(issues[0].file.as_deref(), Some("a.rs"));
+        assert_eq!(issues[0].line, Some(1));
     }
 
-    // 建议
-    if !result.suggestions.is_empty() {
-        println!("## Suggestions");
-        println!();
-        for suggestion in &result.suggestions {
-            println!("- {}", suggestion);
-        }
-        println!();
+    #[test]
+    fn test_count_at_or_above() {
+        let result = result_with(&[
+            IssueSeverity::Info,
+            IssueSeverity::Warning,
+            IssueSeverity::Critical,
+        ]);
+        assert_eq!(count_at_or_above(&result, IssueSeverity::Critical), 1);
+        assert_eq!(count_at_or_above(&result, IssueSeverity::Warning), 2);
+        assert_eq!(count_at_or_above(&result, IssueSeverity::Info), 3);
+        assert_eq!(count_at_or_above(&result_with(&[]), IssueSeverity::Info), 0);
     }
 }
@@ -0,0 +1,246 @@
+//! 审查基线：不再重复报告的已知问题
+//!
+//! 基线文件 `.gcop/review-baseline` 提交在仓库中，每行一个问题指纹。指纹由文件路径、
+//! 规范化后的描述和问题附近的代码内容计算，不依赖行号，代码移动后仍能匹配。
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::error::Result;
+use crate::git::diff::line_context;
+use crate::llm::ReviewIssue;
+
+/// 基线文件相对仓库根目录的路径
+pub const BASELINE_PATH: &str = ".gcop/review-baseline";
+
+/// 指纹取问题行前后各几行代码
+const CONTEXT_RADIUS: usize = 2;
+
+/// 指纹长度（十六进制字符数）
+const FINGERPRINT_LEN: usize = 16;
+
+/// 基线条目说明的最大长度
+const MAX_LABEL_LEN: usize = 100;
+
+const HEADER: &str = "\
+# gcop-rs review baseline: known issues that `gcop-rs review` will not report again.
+# One fingerprint per line; the text after it is only a reminder of the issue.
+# Update with `gcop-rs review --update-baseline <target>`.
+";
+
+/// 已知问题的指纹集合
+#[derive(Debug, Default)]
+pub struct Baseline {
+    /// 指纹 -> 说明
+    entries: BTreeMap<String, String>,
+}
+
+impl Baseline {
+    /// 读取基线文件，不存在时返回空基线
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 解析基线内容：忽略空行和 `#` 注释，每行第一个字段为指纹
+    pub fn parse(content: &str) -> Self {
+        let entries = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (fingerprint, label) =
+                    line.split_once(char::is_whitespace).unwrap_or((line, ""));
+                (fingerprint.to_string(), label.trim().to_string())
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// 渲染为文件内容，按说明排序，便于在 PR 中审阅
+    pub fn render(&self) -> String {
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0)));
+        let mut out = HEADER.to_string();
+        for (fingerprint, label) in entries {
+            out.push_str(fingerprint);
+            if !label.is_empty() {
+                out.push(' ');
+                out.push_str(label);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// 写入基线文件（必要时创建 `.gcop` 目录）
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.render())?;
+        Ok(())
+    }
+
+    pub fn contains(&self, fingerprint: &str) -> bool {
+        self.entries.contains_key(fingerprint)
+    }
+
+    /// 加入一个问题，已存在时返回 false
+    pub fn insert(&mut self, issue: &ReviewIssue, diff: &str) -> bool {
+        let fingerprint = fingerprint(issue, diff);
+        if self.contains(&fingerprint) {
+            return false;
+        }
+        self.entries.insert(fingerprint, label(issue));
+        true
+    }
+}
+
+/// 移除基线中已有的问题，返回移除的数量
+pub fn suppress(issues: &mut Vec<ReviewIssue>, baseline: &Baseline, diff: &str) -> usize {
+    let before = issues.len();
+    issues.retain(|issue| !baseline.contains(&fingerprint(issue, diff)));
+    before - issues.len()
+}
+
+/// 问题指纹：文件 + 规范化描述 + 问题附近代码内容的哈希
+pub fn fingerprint(issue: &ReviewIssue, diff: &str) -> String {
+    let file = issue
+        .file
+        .as_deref()
+        .map(normalize_file)
+        .unwrap_or_default();
+    let context = issue
+        .line
+        .filter(|_| !file.is_empty())
+        .and_then(|line| line_context(diff, &file, line, CONTEXT_RADIUS))
+        .unwrap_or_default()
+        .join("\n");
+
+    let mut hasher = Sha256::new();
+    hasher.update(file.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(normalize_description(&issue.description).as_bytes());
+    hasher.update(b"\0");
+    hasher.update(context.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>()[..FINGERPRINT_LEN]
+        .to_string()
+}
+
+fn normalize_file(file: &str) -> String {
+    let file = file.trim().replace('\\', "/");
+    ["./", "a/", "b/"]
+        .iter()
+        .find_map(|prefix| file.strip_prefix(prefix))
+        .unwrap_or(&file)
+        .to_string()
+}
+
+/// 描述只保留小写的字母数字单词，去掉纯数字（行号、计数等常随运行变化）
+fn normalize_description(description: &str) -> String {
+    description
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !word.chars().all(|c| c.is_ascii_digit()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 基线条目的说明：文件和单行描述
+fn label(issue: &ReviewIssue) -> String {
+    let description = issue
+        .description
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let label = match issue.file.as_deref() {
+        Some(file) => format!("{} {}", normalize_file(file), description),
+        None => description,
+    };
+    match label.char_indices().nth(MAX_LABEL_LEN) {
+        Some((end, _)) => format!("{}...", &label[..end]),
+        None => label,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::IssueSeverity;
+    use pretty_assertions::assert_eq;
+
+    const DIFF: &str = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1,2 +1,3 @@\n fn main() {\n+    let x = y.unwrap();\n }\n";
+
+    fn issue(description: &str, file: Option<&str>, line: Option<usize>) -> ReviewIssue {
+        ReviewIssue {
+            severity: IssueSeverity::Warning,
+            description: description.to_string(),
+            file: file.map(String::from),
+            line,
+            patch: None,
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_wording_noise_and_line_shift() {
+        let a = issue("Unchecked unwrap (line 2)!", Some("src/a.rs"), Some(2));
+        let b = issue("unchecked   UNWRAP line 7", Some("./src/a.rs"), Some(2));
+        assert_eq!(fingerprint(&a, DIFF), fingerprint(&b, DIFF));
+
+        // 同一段代码移动到别的行号仍然匹配
+        let moved = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -40,2 +40,3 @@\n fn main() {\n+    let x = y.unwrap();\n }\n";
+        let c = issue("Unchecked unwrap (line 41)", Some("src/a.rs"), Some(41));
+        assert_eq!(fingerprint(&a, DIFF), fingerprint(&c, moved));
+
+        let other_file = issue("Unchecked unwrap", Some("src/b.rs"), Some(2));
+        assert_ne!(fingerprint(&a, DIFF), fingerprint(&other_file, DIFF));
+        assert_eq!(fingerprint(&a, DIFF).len(), FINGERPRINT_LEN);
+    }
+
+    #[test]
+    fn test_suppress_and_round_trip() {
+        let mut baseline = Baseline::default();
+        assert!(baseline.insert(&issue("Unchecked unwrap", Some("src/a.rs"), Some(2)), DIFF));
+        assert!(!baseline.insert(&issue("Unchecked unwrap", Some("src/a.rs"), Some(2)), DIFF));
+        assert!(baseline.insert(&issue("No tests", None, None), DIFF));
+
+        let reloaded = Baseline::parse(&baseline.render());
+        let mut issues = vec![
+            issue("Unchecked unwrap", Some("src/a.rs"), Some(2)),
+            issue("Magic number", Some("src/a.rs"), Some(2)),
+            issue("no tests.", None, None),
+        ];
+        assert_eq!(suppress(&mut issues, &reloaded, DIFF), 2);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].description, "Magic number");
+    }
+
+    #[test]
+    fn test_render_sorted_by_label() {
+        let mut baseline = Baseline::parse("# comment\n\nbbbb src/z.rs Later\naaaa\n");
+        baseline.insert(&issue("Earlier", Some("src/m.rs"), None), DIFF);
+        let rendered = baseline.render();
+        let body: Vec<&str> = rendered.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(body.len(), 3);
+        assert_eq!(body[0], "aaaa");
+        assert!(body[1].ends_with(" src/m.rs Earlier"));
+        assert_eq!(body[2], "bbbb src/z.rs Later");
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let baseline = Baseline::load(&dir.path().join("missing")).unwrap();
+        assert!(!baseline.contains("anything"));
+    }
+}
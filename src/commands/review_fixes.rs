@@ -0,0 +1,201 @@
+//! 审查问题附带的修复补丁
+//!
+//! 模型可以为问题给出 unified diff 形式的修复。审查结束后先整理补丁并对工作区做
+//! dry-run 检查，不能干净应用的补丁直接丢弃；应用时再检查一次，失败则不改动任何文件。
+
+use colored::Colorize;
+
+use crate::error::Result;
+use crate::git::GitOperations;
+use crate::git::diff::{normalize_patch, parse_hunk_new_start};
+use crate::llm::ReviewIssue;
+
+/// 整理并校验问题的补丁，返回被丢弃的补丁数
+///
+/// 补丁只能修改问题所在的文件（已按审查 diff 校验），没有文件或指向其他文件的补丁
+/// 在交给 git 之前就被丢弃
+pub fn validate_patches(repo: &impl GitOperations, issues: &mut [ReviewIssue]) -> usize {
+    let mut dropped = 0;
+    for issue in issues {
+        let Some(patch) = issue.patch.take() else {
+            continue;
+        };
+        let normalized = issue
+            .file
+            .as_deref()
+            .and_then(|file| normalize_patch(&patch, file));
+        issue.patch = normalized.filter(|patch| match repo.apply_patch(patch, true) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::debug!("Dropping suggested fix that does not apply: {}", e);
+                false
+            }
+        });
+        if issue.patch.is_none() {
+            dropped += 1;
+        }
+    }
+    dropped
+}
+
+/// 先 dry-run 检查再应用，检查失败时不修改工作区
+pub fn apply_fix(repo: &impl GitOperations, patch: &str) -> Result<()> {
+    repo.apply_patch(patch, true)?;
+    repo.apply_patch(patch, false)
+}
+
+/// 应用全部补丁，返回每个补丁对应的问题序号（从 1 开始）和结果
+///
+/// 同一文件的补丁从下往上应用，避免前面的修改让后面补丁的行号失效
+pub fn apply_all(repo: &impl GitOperations, issues: &[ReviewIssue]) -> Vec<(usize, Result<()>)> {
+    let mut fixes: Vec<(usize, &str)> = issues
+        .iter()
+        .enumerate()
+        .filter_map(|(i, issue)| issue.patch.as_deref().map(|patch| (i + 1, patch)))
+        .collect();
+    fixes.sort_by_key(|(_, patch)| std::cmp::Reverse(patch_position(patch)));
+
+    fixes
+        .into_iter()
+        .map(|(number, patch)| (number, apply_fix(repo, patch)))
+        .collect()
+}
+
+/// 补丁的文件路径和第一个 hunk 的起始行，用于排序
+fn patch_position(patch: &str) -> (String, usize) {
+    let path = patch
+        .lines()
+        .find_map(|line| line.strip_prefix("+++ b/"))
+        .unwrap_or_default()
+        .to_string();
+    let line = patch
+        .lines()
+        .find_map(parse_hunk_new_start)
+        .unwrap_or_default();
+    (path, line)
+}
+
+/// 渲染补丁的 hunk 部分（省略文件头），新增行绿色、删除行红色
+pub fn render_patch(patch: &str, indent: &str, colored: bool) -> String {
+    let mut out = String::new();
+    let hunks = patch.lines().skip_while(|line| !line.starts_with("@@"));
+    for line in hunks {
+        let styled = if !colored {
+            line.to_string()
+        } else if line.starts_with("@@") {
+            line.bright_black().to_string()
+        } else if line.starts_with('+') {
+            line.green().to_string()
+        } else if line.starts_with('-') {
+            line.red().to_string()
+        } else {
+            line.to_string()
+        };
+        out.push_str(indent);
+        out.push_str(&styled);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::GcopError;
+    use crate::git::MockGitOperations;
+    use crate::llm::IssueSeverity;
+    use pretty_assertions::assert_eq;
+
+    fn issue(file: &str, patch: Option<&str>) -> ReviewIssue {
+        ReviewIssue {
+            severity: IssueSeverity::Warning,
+            description: "issue".to_string(),
+            file: Some(file.to_string()),
+            line: Some(1),
+            patch: patch.map(String::from),
+        }
+    }
+
+    fn patch(file: &str, start: usize) -> String {
+        format!(
+            "diff --git a/{file} b/{file}\n--- a/{file}\n+++ b/{file}\n@@ -{start},1 +{start},1 @@\n-a\n+b\n"
+        )
+    }
+
+    #[test]
+    fn test_validate_patches_drops_unusable() {
+        let mut repo = MockGitOperations::new();
+        repo.expect_apply_patch()
+            .withf(|patch, check_only| *check_only && patch.contains("ok.rs"))
+            .returning(|_, _| Ok(()));
+        repo.expect_apply_patch()
+            .withf(|patch, _| patch.contains("stale.rs"))
+            .returning(|_, _| Err(GcopError::Other("conflict".to_string())));
+
+        let mut issues = vec![
+            issue("ok.rs", Some("@@ -1 +1 @@\n-a\n+b\n")),
+            issue("stale.rs", Some("@@ -1 +1 @@\n-a\n+b\n")),
+            issue("prose.rs", Some("Use ? instead of unwrap")),
+            issue("none.rs", None),
+        ];
+        assert_eq!(validate_patches(&repo, &mut issues), 2);
+        assert_eq!(issues[0].patch.as_deref(), Some(patch("ok.rs", 1).as_str()));
+        assert!(issues[1..].iter().all(|issue| issue.patch.is_none()));
+    }
+
+    #[test]
+    fn test_validate_patches_drops_patches_for_other_files() {
+        // 没有设置期望：任何 apply_patch 调用都会让测试失败
+        let repo = MockGitOperations::new();
+        let mut unlocated = issue("a.rs", Some(&patch("a.rs", 1)));
+        unlocated.file = None;
+        let mut issues = vec![
+            issue("src/a.rs", Some(&patch(".git/hooks/pre-commit", 1))),
+            issue(
+                "src/a.rs",
+                Some(&format!("{}{}", patch("src/a.rs", 1), patch("build.rs", 1))),
+            ),
+            unlocated,
+        ];
+        assert_eq!(validate_patches(&repo, &mut issues), 3);
+        assert!(issues.iter().all(|issue| issue.patch.is_none()));
+    }
+
+    #[test]
+    fn test_apply_all_bottom_up() {
+        let mut repo = MockGitOperations::new();
+        let mut seq = mockall::Sequence::new();
+        for start in [40, 5] {
+            for check in [true, false] {
+                let expected = patch("a.rs", start);
+                repo.expect_apply_patch()
+                    .withf(move |patch, check_only| patch == expected && *check_only == check)
+                    .times(1)
+                    .in_sequence(&mut seq)
+                    .returning(|_, _| Ok(()));
+            }
+        }
+
+        let issues = vec![
+            issue("a.rs", Some(&patch("a.rs", 5))),
+            issue("a.rs", None),
+            issue("a.rs", Some(&patch("a.rs", 40))),
+        ];
+        let numbers: Vec<usize> = apply_all(&repo, &issues)
+            .into_iter()
+            .map(|(number, result)| {
+                assert!(result.is_ok());
+                number
+            })
+            .collect();
+        assert_eq!(numbers, vec![3, 1]);
+    }
+
+    #[test]
+    fn test_render_patch_skips_headers() {
+        assert_eq!(
+            render_patch(&patch("a.rs", 3), "  ", false),
+            "  @@ -3,1 +3,1 @@\n  -a\n  +b\n"
+        );
+    }
+}
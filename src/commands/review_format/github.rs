@@ -0,0 +1,105 @@
+//! GitHub Actions workflow commands
+//!
+//! 每个问题输出一行 `::warning file=...,line=...::message`，
+//! 在 Actions 中会显示为 PR diff 上的注解。
+
+use super::{ReviewFormatter, normalize_path};
+use crate::error::Result;
+use crate::llm::{IssueSeverity, ReviewIssue, ReviewResult};
+
+const ANNOTATION_TITLE: &str = "gcop-rs review";
+
+/// GitHub Actions 注解
+pub struct GithubFormatter;
+
+/// 严重性对应的 workflow command
+fn command(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Critical => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info => "notice",
+    }
+}
+
+/// 转义 message 部分
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// 转义属性值（额外转义 `:` 和 `,`）
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn annotation(issue: &ReviewIssue) -> String {
+    let mut properties = Vec::new();
+    if let Some(file) = issue.file.as_deref().filter(|f| !f.trim().is_empty()) {
+        properties.push(format!("file={}", escape_property(&normalize_path(file))));
+        if let Some(line) = issue.line.filter(|line| *line > 0) {
+            properties.push(format!("line={}", line));
+        }
+    }
+    properties.push(format!("title={}", escape_property(ANNOTATION_TITLE)));
+
+    format!(
+        "::{} {}::{}",
+        command(issue.severity),
+        properties.join(","),
+        escape_data(&issue.description)
+    )
+}
+
+impl ReviewFormatter for GithubFormatter {
+    fn render(&self, result: &ReviewResult, _description: &str) -> Result<String> {
+        let mut out = String::new();
+        for issue in &result.issues {
+            out.push_str(&annotation(issue));
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    // workflow command 混在普通日志中也能被识别，保留进度输出
+    fn is_machine_readable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_annotation_with_location() {
+        let issue = ReviewIssue {
+            severity: IssueSeverity::Warning,
+            description: "Possible panic\non empty input: 100%".to_string(),
+            file: Some("./src/a,b.rs".to_string()),
+            line: Some(12),
+            patch: None,
+        };
+        assert_eq!(
+            annotation(&issue),
+            "::warning file=src/a%2Cb.rs,line=12,title=gcop-rs review::Possible panic%0Aon empty input: 100%25"
+        );
+    }
+
+    #[test]
+    fn test_annotation_without_location() {
+        let issue = ReviewIssue {
+            severity: IssueSeverity::Critical,
+            description: "Leaks credentials".to_string(),
+            file: None,
+            line: Some(3),
+            patch: None,
+        };
+        assert_eq!(
+            annotation(&issue),
+            "::error title=gcop-rs review::Leaks credentials"
+        );
+    }
+}
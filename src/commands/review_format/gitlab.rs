@@ -0,0 +1,139 @@
+//! GitLab Code Quality 报告
+//!
+//! 输出 Code Climate 格式的 JSON 数组，作为 `artifacts:reports:codequality` 上传后
+//! 会显示在 MR 的代码质量面板中。
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::{ReviewFormatter, normalize_path};
+use crate::error::Result;
+use crate::llm::{IssueSeverity, ReviewIssue, ReviewResult};
+
+const CHECK_PREFIX: &str = "gcop-rs";
+
+/// 未给出文件的问题归到仓库根目录
+const FALLBACK_PATH: &str = ".";
+
+/// GitLab Code Quality JSON
+pub struct GitlabFormatter;
+
+#[derive(Debug, Serialize)]
+struct CodeQualityIssue {
+    description: String,
+    check_name: String,
+    fingerprint: String,
+    severity: &'static str,
+    location: CodeQualityLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+#[derive(Debug, Serialize)]
+struct CodeQualityLines {
+    begin: usize,
+}
+
+/// 严重性对应的 Code Quality 等级（info | minor | major | critical | blocker）
+fn gitlab_severity(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Critical => "critical",
+        IssueSeverity::Warning => "major",
+        IssueSeverity::Info => "info",
+    }
+}
+
+/// 基于问题内容的稳定指纹，GitLab 用它比较前后两次报告
+fn fingerprint(severity: &str, path: &str, line: usize, description: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(severity.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(line.to_string().as_bytes());
+    hasher.update(b"\0");
+    hasher.update(description.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn to_code_quality(issue: &ReviewIssue) -> CodeQualityIssue {
+    let path = issue
+        .file
+        .as_deref()
+        .filter(|file| !file.trim().is_empty())
+        .map(normalize_path)
+        .unwrap_or_else(|| FALLBACK_PATH.to_string());
+    let line = issue.line.filter(|line| *line > 0).unwrap_or(1);
+    let severity = gitlab_severity(issue.severity);
+
+    CodeQualityIssue {
+        description: issue.description.clone(),
+        check_name: format!("{}/{}", CHECK_PREFIX, super::severity_name(issue.severity)),
+        fingerprint: fingerprint(severity, &path, line, &issue.description),
+        severity,
+        location: CodeQualityLocation {
+            path,
+            lines: CodeQualityLines { begin: line },
+        },
+    }
+}
+
+impl ReviewFormatter for GitlabFormatter {
+    fn render(&self, result: &ReviewResult, _description: &str) -> Result<String> {
+        let issues: Vec<_> = result.issues.iter().map(to_code_quality).collect();
+        Ok(format!("{}\n", serde_json::to_string_pretty(&issues)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_code_quality_issue() {
+        let issue = ReviewIssue {
+            severity: IssueSeverity::Warning,
+            description: "Unchecked unwrap".to_string(),
+            file: Some("./src/main.rs".to_string()),
+            line: Some(7),
+            patch: None,
+        };
+        let value = serde_json::to_value(to_code_quality(&issue)).unwrap();
+        assert_eq!(value["severity"], "major");
+        assert_eq!(value["check_name"], "gcop-rs/warning");
+        assert_eq!(value["location"]["path"], "src/main.rs");
+        assert_eq!(value["location"]["lines"]["begin"], 7);
+        assert_eq!(value["fingerprint"].as_str().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_issue_without_location_uses_fallback() {
+        let issue = ReviewIssue {
+            severity: IssueSeverity::Critical,
+            description: "Design flaw".to_string(),
+            file: None,
+            line: None,
+            patch: None,
+        };
+        let quality = to_code_quality(&issue);
+        assert_eq!(quality.location.path, ".");
+        assert_eq!(quality.location.lines.begin, 1);
+        assert_eq!(quality.severity, "critical");
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_and_distinct() {
+        let a = fingerprint("major", "src/a.rs", 1, "x");
+        assert_eq!(a, fingerprint("major", "src/a.rs", 1, "x"));
+        assert_ne!(a, fingerprint("major", "src/a.rs", 2, "x"));
+    }
+}
@@ -0,0 +1,156 @@
+//! JUnit XML 报告
+//!
+//! 每个问题是一个失败的 testcase（`type` 为严重性），没有问题时输出一个通过的
+//! testcase，便于 CI 的测试报告面板展示。
+
+use super::{ReviewFormatter, normalize_path, severity_name};
+use crate::error::Result;
+use crate::llm::{ReviewIssue, ReviewResult};
+
+const SUITE_NAME: &str = "gcop-rs review";
+const CLASS_NAME: &str = "gcop-rs.review";
+
+/// JUnit XML
+pub struct JunitFormatter;
+
+/// 转义 XML 文本和属性值
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 不允许除 \t \n \r 之外的控制字符
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// testcase 名称：位置 + 严重性
+fn testcase_name(index: usize, issue: &ReviewIssue) -> String {
+    let location = match (issue.file.as_deref(), issue.line) {
+        (Some(file), Some(line)) if line > 0 => format!("{}:{}", normalize_path(file), line),
+        (Some(file), _) => normalize_path(file),
+        (None, _) => "general".to_string(),
+    };
+    format!(
+        "{}. [{}] {}",
+        index + 1,
+        severity_name(issue.severity),
+        location
+    )
+}
+
+impl ReviewFormatter for JunitFormatter {
+    fn render(&self, result: &ReviewResult, description: &str) -> Result<String> {
+        let failures = result.issues.len();
+        let tests = failures.max(1);
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            escape_xml(SUITE_NAME),
+            tests,
+            failures
+        ));
+        out.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+            escape_xml(description),
+            tests,
+            failures
+        ));
+
+        if result.issues.is_empty() {
+            out.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"{}\"/>\n",
+                CLASS_NAME,
+                escape_xml(description)
+            ));
+        }
+        for (i, issue) in result.issues.iter().enumerate() {
+            out.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"{}\">\n",
+                CLASS_NAME,
+                escape_xml(&testcase_name(i, issue))
+            ));
+            out.push_str(&format!(
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                escape_xml(&issue.description),
+                severity_name(issue.severity),
+                escape_xml(&issue.description)
+            ));
+            out.push_str("    </testcase>\n");
+        }
+
+        out.push_str(&format!(
+            "    <system-out>{}</system-out>\n",
+            escape_xml(&result.summary)
+        ));
+        out.push_str("  </testsuite>\n</testsuites>\n");
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::IssueSeverity;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("a < b && \"c\" > 'd'\u{1}"),
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; &apos;d&apos;"
+        );
+    }
+
+    #[test]
+    fn test_junit_with_issues() {
+        let result = ReviewResult {
+            summary: "Needs work".to_string(),
+            issues: vec![ReviewIssue {
+                severity: IssueSeverity::Critical,
+                description: "SQL built with <format!>".to_string(),
+                file: Some("src/db.rs".to_string()),
+                line: Some(9),
+                patch: None,
+            }],
+            suggestions: vec![],
+        };
+        let xml = JunitFormatter.render(&result, "Commit abc").unwrap();
+        assert_eq!(
+            xml,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <testsuites name=\"gcop-rs review\" tests=\"1\" failures=\"1\">\n\
+             \x20 <testsuite name=\"Commit abc\" tests=\"1\" failures=\"1\" errors=\"0\" skipped=\"0\">\n\
+             \x20   <testcase classname=\"gcop-rs.review\" name=\"1. [critical] src/db.rs:9\">\n\
+             \x20     <failure message=\"SQL built with &lt;format!&gt;\" type=\"critical\">SQL built with &lt;format!&gt;</failure>\n\
+             \x20   </testcase>\n\
+             \x20   <system-out>Needs work</system-out>\n\
+             \x20 </testsuite>\n\
+             </testsuites>\n"
+        );
+    }
+
+    #[test]
+    fn test_junit_without_issues_has_passing_case() {
+        let result = ReviewResult {
+            summary: "All good".to_string(),
+            issues: vec![],
+            suggestions: vec![],
+        };
+        let xml = JunitFormatter
+            .render(&result, "Uncommitted changes")
+            .unwrap();
+        assert!(xml.contains("tests=\"1\" failures=\"0\""));
+        assert!(
+            xml.contains("<testcase classname=\"gcop-rs.review\" name=\"Uncommitted changes\"/>")
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use super::ReviewFormatter;
+use crate::error::Result;
+use crate::llm::{IssueSeverity, ReviewResult};
+
+/// Markdown 报告，适合贴到 PR 评论或存档
+pub struct MarkdownFormatter;
+
+impl ReviewFormatter for MarkdownFormatter {
+    fn render(&self, result: &ReviewResult, description: &str) -> Result<String> {
+        let mut out = format!("# Code Review: {}\n\n", description);
+
+        // 摘要
+        out.push_str("## Summary\n\n");
+        out.push_str(&format!("{}\n\n", result.summary));
+
+        // 问题
+        out.push_str("## Issues\n\n");
+        if result.issues.is_empty() {
+            out.push_str("✨ No issues found!\n\n");
+        }
+        for issue in &result.issues {
+            let (severity_emoji, severity_text) = match issue.severity {
+                IssueSeverity::Critical => ("🔴", "**CRITICAL**"),
+                IssueSeverity::Warning => ("🟡", "**WARNING**"),
+                IssueSeverity::Info => ("🔵", "**INFO**"),
+            };
+
+            out.push_str(&format!("### {} {}\n\n", severity_emoji, severity_text));
+            out.push_str(&format!("{}\n\n", issue.description));
+
+            if let Some(file) = &issue.file {
+                if let Some(line) = issue.line {
+                    out.push_str(&format!("**Location:** `{}:{}`\n\n", file, line));
+                } else {
+                    out.push_str(&format!("**Location:** `{}`\n\n", file));
+                }
+            }
+            if let Some(patch) = &issue.patch {
+                out.push_str(&format!("**Suggested fix:**\n\n```diff\n{}```\n\n", patch));
+            }
+        }
+
+        // 建议
+        if !result.suggestions.is_empty() {
+            out.push_str("## Suggestions\n\n");
+            for suggestion in &result.suggestions {
+                out.push_str(&format!("- {}\n", suggestion));
+            }
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+
+    fn is_machine_readable(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,108 @@
+//! 审查结果的输出格式
+//!
+//! 每种 `review --format` 对应一个 [`ReviewFormatter`] 实现，
+//! 新增格式只需实现该 trait 并在 [`formatter_for`] 中注册。
+
+mod github;
+mod gitlab;
+mod junit;
+mod markdown;
+mod sarif;
+mod text;
+
+pub use github::GithubFormatter;
+pub use gitlab::GitlabFormatter;
+pub use junit::JunitFormatter;
+pub use markdown::MarkdownFormatter;
+pub use sarif::SarifFormatter;
+pub use text::TextFormatter;
+
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::llm::{IssueSeverity, ReviewResult};
+
+/// 审查结果渲染器
+pub trait ReviewFormatter {
+    /// 将审查结果渲染为完整输出（含结尾换行）
+    fn render(&self, result: &ReviewResult, description: &str) -> Result<String>;
+
+    /// 输出是否供程序解析；为 true 时 stdout 只包含报告本身，不打印进度
+    fn is_machine_readable(&self) -> bool {
+        true
+    }
+}
+
+/// 原样输出 `ReviewResult` 的 JSON
+pub struct JsonFormatter;
+
+impl ReviewFormatter for JsonFormatter {
+    fn render(&self, result: &ReviewResult, _description: &str) -> Result<String> {
+        Ok(format!("{}\n", serde_json::to_string_pretty(result)?))
+    }
+}
+
+/// 根据 `--format` 的值选择渲染器，未知格式按 text 处理
+pub fn formatter_for(format: &str, config: &AppConfig) -> Box<dyn ReviewFormatter> {
+    match format {
+        "json" => Box::new(JsonFormatter),
+        "markdown" => Box::new(MarkdownFormatter),
+        "sarif" => Box::new(SarifFormatter),
+        "github" => Box::new(GithubFormatter),
+        "gitlab" => Box::new(GitlabFormatter),
+        "junit" => Box::new(JunitFormatter),
+        _ => Box::new(TextFormatter::from_config(config)),
+    }
+}
+
+/// 严重性的小写名称（与配置和 JSON 输出一致）
+fn severity_name(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Critical => "critical",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info => "info",
+    }
+}
+
+/// 规范化 LLM 给出的文件路径为仓库内的相对路径
+fn normalize_path(file: &str) -> String {
+    let path = file.trim().replace('\\', "/");
+    let path = path
+        .strip_prefix("./")
+        .or_else(|| path.strip_prefix("a/"))
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(&path);
+    path.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_formatter_for_machine_readable() {
+        let config = AppConfig::default();
+        for format in ["json", "sarif", "gitlab", "junit"] {
+            assert!(
+                formatter_for(format, &config).is_machine_readable(),
+                "{}",
+                format
+            );
+        }
+        for format in ["text", "markdown", "github", "unknown"] {
+            assert!(
+                !formatter_for(format, &config).is_machine_readable(),
+                "{}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("./src/main.rs"), "src/main.rs");
+        assert_eq!(normalize_path("b/src/lib.rs"), "src/lib.rs");
+        assert_eq!(normalize_path("src\\win.rs"), "src/win.rs");
+        assert_eq!(normalize_path("src/ok.rs"), "src/ok.rs");
+    }
+}
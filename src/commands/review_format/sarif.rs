@@ -0,0 +1,284 @@
+//! SARIF 2.1.0 输出
+//!
+//! 将审查结果转换为 SARIF 日志，供 GitHub code scanning 等平台导入。
+//! 每个 [`ReviewIssue`] 对应一条 result，按严重性归入三条规则。
+
+use serde::Serialize;
+
+use super::{ReviewFormatter, normalize_path};
+use crate::error::Result;
+use crate::llm::{IssueSeverity, ReviewIssue, ReviewResult};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const INFORMATION_URI: &str = "https://github.com/AptS-1547/gcop-rs";
+
+/// 源码根目录的 uriBaseId，路径均相对于仓库根目录
+const SRCROOT: &str = "%SRCROOT%";
+
+/// 按严重性排列的规则：(严重性, 规则 id, 说明)
+const RULES: &[(IssueSeverity, &str, &str)] = &[
+    (
+        IssueSeverity::Critical,
+        "gcop-rs/critical",
+        "Critical issue found by AI code review",
+    ),
+    (
+        IssueSeverity::Warning,
+        "gcop-rs/warning",
+        "Warning found by AI code review",
+    ),
+    (
+        IssueSeverity::Info,
+        "gcop-rs/info",
+        "Suggestion from AI code review",
+    ),
+];
+
+#[derive(Debug, Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: &'static str,
+    short_description: SarifMessage,
+    default_configuration: SarifConfiguration,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifConfiguration {
+    level: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    rule_index: usize,
+    level: &'static str,
+    message: SarifMessage,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactLocation {
+    uri: String,
+    uri_base_id: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+}
+
+/// 严重性对应的 SARIF level
+fn sarif_level(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Critical => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info => "note",
+    }
+}
+
+fn to_result(issue: &ReviewIssue) -> SarifResult {
+    let rule_index = RULES
+        .iter()
+        .position(|(severity, _, _)| *severity == issue.severity)
+        .unwrap_or(RULES.len() - 1);
+
+    let locations = issue
+        .file
+        .as_deref()
+        .filter(|file| !file.trim().is_empty())
+        .map(|file| SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: normalize_path(file),
+                    uri_base_id: SRCROOT,
+                },
+                // SARIF 行号从 1 开始
+                region: issue
+                    .line
+                    .filter(|line| *line > 0)
+                    .map(|start_line| SarifRegion { start_line }),
+            },
+        })
+        .into_iter()
+        .collect();
+
+    SarifResult {
+        rule_id: RULES[rule_index].1,
+        rule_index,
+        level: sarif_level(issue.severity),
+        message: SarifMessage {
+            text: issue.description.clone(),
+        },
+        locations,
+    }
+}
+
+/// 将审查结果转换为 SARIF 日志
+fn to_sarif(result: &ReviewResult) -> SarifLog {
+    let rules = RULES
+        .iter()
+        .map(|(severity, id, description)| SarifRule {
+            id,
+            short_description: SarifMessage {
+                text: description.to_string(),
+            },
+            default_configuration: SarifConfiguration {
+                level: sarif_level(*severity),
+            },
+        })
+        .collect();
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "gcop-rs",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: INFORMATION_URI,
+                    rules,
+                },
+            },
+            results: result.issues.iter().map(to_result).collect(),
+        }],
+    }
+}
+
+/// SARIF 2.1.0 日志，供 code scanning 平台导入
+pub struct SarifFormatter;
+
+impl ReviewFormatter for SarifFormatter {
+    fn render(&self, result: &ReviewResult, _description: &str) -> Result<String> {
+        Ok(format!(
+            "{}\n",
+            serde_json::to_string_pretty(&to_sarif(result))?
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn issue(severity: IssueSeverity, file: Option<&str>, line: Option<usize>) -> ReviewIssue {
+        ReviewIssue {
+            severity,
+            description: "Something is off".to_string(),
+            file: file.map(String::from),
+            line,
+            patch: None,
+        }
+    }
+
+    fn render(issues: Vec<ReviewIssue>) -> serde_json::Value {
+        let result = ReviewResult {
+            summary: "summary".to_string(),
+            issues,
+            suggestions: vec![],
+        };
+        serde_json::to_value(to_sarif(&result)).unwrap()
+    }
+
+    #[test]
+    fn test_sarif_envelope() {
+        let log = render(vec![]);
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["$schema"], SARIF_SCHEMA);
+        assert_eq!(log["runs"][0]["tool"]["driver"]["name"], "gcop-rs");
+        assert_eq!(
+            log["runs"][0]["tool"]["driver"]["rules"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(log["runs"][0]["results"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_severity_maps_to_level_and_rule() {
+        let log = render(vec![
+            issue(IssueSeverity::Critical, None, None),
+            issue(IssueSeverity::Warning, None, None),
+            issue(IssueSeverity::Info, None, None),
+        ]);
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        let levels: Vec<_> = results.iter().map(|r| r["level"].clone()).collect();
+        assert_eq!(levels, vec!["error", "warning", "note"]);
+        assert_eq!(results[1]["ruleId"], "gcop-rs/warning");
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert!(results[0].get("locations").is_none());
+    }
+
+    #[test]
+    fn test_physical_location() {
+        let log = render(vec![
+            issue(IssueSeverity::Warning, Some("./src/main.rs"), Some(42)),
+            issue(IssueSeverity::Warning, Some("b/src/lib.rs"), Some(0)),
+        ]);
+        let results = &log["runs"][0]["results"];
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(location["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        assert_eq!(location["region"]["startLine"], 42);
+
+        let location = &results[1]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert!(location.get("region").is_none());
+    }
+}
@@ -0,0 +1,150 @@
+use colored::Colorize;
+
+use super::ReviewFormatter;
+use crate::commands::review_fixes::render_patch;
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::llm::{IssueSeverity, ReviewResult};
+use crate::ui;
+
+/// 终端文本输出，按 `review.min_severity` 过滤问题
+pub struct TextFormatter {
+    colored: bool,
+    min_severity: IssueSeverity,
+}
+
+impl TextFormatter {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            colored: config.ui.colored,
+            min_severity: IssueSeverity::from_config(&config.review.min_severity),
+        }
+    }
+
+    /// 格式化严重性标签
+    fn severity_label(&self, severity: IssueSeverity) -> String {
+        let label = match severity {
+            IssueSeverity::Critical => "CRITICAL",
+            IssueSeverity::Warning => "WARNING",
+            IssueSeverity::Info => "INFO",
+        };
+
+        if !self.colored {
+            return format!("[{}]", label);
+        }
+        match severity {
+            IssueSeverity::Critical => label.red().bold().to_string(),
+            IssueSeverity::Warning => label.yellow().bold().to_string(),
+            IssueSeverity::Info => label.blue().bold().to_string(),
+        }
+    }
+}
+
+impl ReviewFormatter for TextFormatter {
+    fn render(&self, result: &ReviewResult, description: &str) -> Result<String> {
+        let mut out = String::new();
+
+        out.push_str(&format!(
+            "{}\n\n",
+            ui::info(&format!("Review: {}", description), self.colored)
+        ));
+
+        // 摘要
+        out.push_str("📝 Summary:\n");
+        out.push_str(&format!("{}\n\n", result.summary));
+
+        // 问题
+        if !result.issues.is_empty() {
+            out.push_str("🔍 Issues found:\n\n");
+
+            for (i, issue) in result.issues.iter().enumerate() {
+                // 跳过低于最小严重性的问题
+                if !issue.severity.meets(self.min_severity) {
+                    continue;
+                }
+
+                out.push_str(&format!(
+                    "  {}. {} {}\n",
+                    i + 1,
+                    self.severity_label(issue.severity),
+                    issue.description
+                ));
+
+                // 位置信息
+                if let Some(file) = &issue.file {
+                    if let Some(line) = issue.line {
+                        out.push_str(&format!("     Location: {}:{}\n", file, line));
+                    } else {
+                        out.push_str(&format!("     Location: {}\n", file));
+                    }
+                }
+                if let Some(patch) = &issue.patch {
+                    out.push_str("     Suggested fix:\n");
+                    out.push_str(&render_patch(patch, "       ", self.colored));
+                }
+                out.push('\n');
+            }
+        } else {
+            out.push_str("✨ No issues found!\n\n");
+        }
+
+        // 建议
+        if !result.suggestions.is_empty() {
+            out.push_str("💡 Suggestions:\n\n");
+            for suggestion in &result.suggestions {
+                out.push_str(&format!("  • {}\n", suggestion));
+            }
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+
+    fn is_machine_readable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::ReviewIssue;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_text_filters_by_min_severity() {
+        let formatter = TextFormatter {
+            colored: false,
+            min_severity: IssueSeverity::Warning,
+        };
+        let result = ReviewResult {
+            summary: "Looks fine".to_string(),
+            issues: vec![
+                ReviewIssue {
+                    severity: IssueSeverity::Info,
+                    description: "Nit".to_string(),
+                    file: None,
+                    line: None,
+                    patch: None,
+                },
+                ReviewIssue {
+                    severity: IssueSeverity::Warning,
+                    description: "Unchecked unwrap".to_string(),
+                    file: Some("src/main.rs".to_string()),
+                    line: Some(7),
+                    patch: Some(
+                        "diff --git a/src/main.rs b/src/main.rs\n--- a/src/main.rs\n+++ b/src/main.rs\n@@ -7,1 +7,1 @@\n-x.unwrap()\n+x?\n"
+                            .to_string(),
+                    ),
+                },
+            ],
+            suggestions: vec!["Add tests".to_string()],
+        };
+
+        let out = formatter.render(&result, "Commit abc").unwrap();
+        assert_eq!(
+            out,
+            "ℹ Review: Commit abc\n\n📝 Summary:\nLooks fine\n\n🔍 Issues found:\n\n  2. [WARNING] Unchecked unwrap\n     Location: src/main.rs:7\n     Suggested fix:\n       @@ -7,1 +7,1 @@\n       -x.unwrap()\n       +x?\n\n💡 Suggestions:\n\n  • Add tests\n\n"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! 仓库级审查规则
+//!
+//! 规则放在仓库中的 `.gcop/review.md` 或 `.gcop/review.toml`，可按路径 glob 限定范围。
+//! 每次请求只把与 diff 中文件相关的规则加入审查 prompt。
+//!
+//! Markdown 中标题里用反引号写出的 glob 限定该节的范围，其余内容对所有文件生效：
+//!
+//! ```markdown
+//! Prefer explicit error types.
+//!
+//! ## `db/**`
+//! Migrations must be reversible.
+//! ```
+//!
+//! TOML 使用 `[[rules]]` 数组，`paths` 省略时对所有文件生效：
+//!
+//! ```toml
+//! [[rules]]
+//! paths = ["db/**"]
+//! text = "Migrations must be reversible."
+//! ```
+
+use std::path::Path;
+
+use globset::GlobSet;
+use serde::Deserialize;
+
+use crate::commands::preflight::build_path_matcher;
+use crate::error::{GcopError, Result};
+
+/// Markdown 规则文件相对仓库根目录的路径
+pub const RULES_MARKDOWN_PATH: &str = ".gcop/review.md";
+
+/// TOML 规则文件相对仓库根目录的路径
+pub const RULES_TOML_PATH: &str = ".gcop/review.toml";
+
+/// 一组规则
+#[derive(Debug)]
+struct RuleSet {
+    /// 生效的路径 glob，为空时对所有文件生效
+    paths: Vec<String>,
+    matcher: GlobSet,
+    text: String,
+}
+
+impl RuleSet {
+    fn new(paths: Vec<String>, text: &str, source: &str) -> Result<Self> {
+        Ok(Self {
+            matcher: build_path_matcher(&paths, source)?,
+            paths,
+            text: text.trim().to_string(),
+        })
+    }
+
+    fn applies_to(&self, files: &[String]) -> bool {
+        self.paths.is_empty() || files.iter().any(|file| self.matcher.is_match(file))
+    }
+}
+
+/// 仓库的审查规则
+#[derive(Debug, Default)]
+pub struct ReviewRules {
+    sets: Vec<RuleSet>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TomlRules {
+    #[serde(default)]
+    rules: Vec<TomlRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TomlRule {
+    #[serde(default)]
+    paths: Vec<String>,
+    text: String,
+}
+
+impl ReviewRules {
+    /// 读取仓库根目录下的规则文件，两个文件都存在时合并，都不存在时返回空规则
+    pub fn load(root: &Path) -> Result<Self> {
+        let mut rules = Self::default();
+        if let Some(content) = read_optional(&root.join(RULES_MARKDOWN_PATH))? {
+            rules.sets.extend(Self::parse_markdown(&content)?.sets);
+        }
+        if let Some(content) = read_optional(&root.join(RULES_TOML_PATH))? {
+            rules.sets.extend(Self::parse_toml(&content)?.sets);
+        }
+        Ok(rules)
+    }
+
+    /// 解析 Markdown 规则：标题中的反引号 glob 开始一个限定范围的节
+    pub fn parse_markdown(content: &str) -> Result<Self> {
+        let mut sections: Vec<(Vec<String>, String)> = vec![(Vec::new(), String::new())];
+        for line in content.lines() {
+            if line.starts_with('#') {
+                let globs = backtick_spans(line);
+                if !globs.is_empty() {
+                    sections.push((globs, String::new()));
+                    continue;
+                }
+                // 普通标题结束限定范围的节，之后的内容重新对所有文件生效
+                if sections.last().is_some_and(|(paths, _)| !paths.is_empty()) {
+                    sections.push((Vec::new(), String::new()));
+                }
+            }
+            if let Some((_, text)) = sections.last_mut() {
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+
+        let sets = sections
+            .into_iter()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .map(|(paths, text)| RuleSet::new(paths, &text, RULES_MARKDOWN_PATH))
+            .collect::<Result<_>>()?;
+        Ok(Self { sets })
+    }
+
+    /// 解析 TOML 规则
+    pub fn parse_toml(content: &str) -> Result<Self> {
+        let parsed: TomlRules = toml::from_str(content)
+            .map_err(|e| GcopError::Config(format!("Invalid {}: {}", RULES_TOML_PATH, e)))?;
+        let sets = parsed
+            .rules
+            .into_iter()
+            .filter(|rule| !rule.text.trim().is_empty())
+            .map(|rule| RuleSet::new(rule.paths, &rule.text, RULES_TOML_PATH))
+            .collect::<Result<_>>()?;
+        Ok(Self { sets })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// 渲染与给定文件相关的规则，没有相关规则时返回 None
+    pub fn render_for(&self, files: &[String]) -> Option<String> {
+        let sections: Vec<String> = self
+            .sets
+            .iter()
+            .filter(|set| set.applies_to(files))
+            .map(|set| {
+                if set.paths.is_empty() {
+                    set.text.clone()
+                } else {
+                    let scope: Vec<String> = set.paths.iter().map(|p| format!("`{}`", p)).collect();
+                    format!("For files matching {}:\n{}", scope.join(", "), set.text)
+                }
+            })
+            .collect();
+        (!sections.is_empty()).then(|| sections.join("\n\n"))
+    }
+}
+
+/// 读取文件，不存在时返回 None
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 提取一行中所有反引号包围的片段
+fn backtick_spans(line: &str) -> Vec<String> {
+    line.split('`')
+        .skip(1)
+        .step_by(2)
+        .map(str::trim)
+        .filter(|span| !span.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const MARKDOWN: &str = "# Review rules\n\nPrefer explicit error types.\n\n## `db/**`\n- Migrations must be reversible.\n\n## `src/**/*.rs` `tests/`\n- No unwrap outside tests.\n\n## Style\nKeep functions short.\n";
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_markdown_rules_scoped_by_globs() {
+        let rules = ReviewRules::parse_markdown(MARKDOWN).unwrap();
+
+        assert_eq!(
+            rules
+                .render_for(&files(&["db/migrations/001.sql"]))
+                .unwrap(),
+            "# Review rules\n\nPrefer explicit error types.\n\n\
+             For files matching `db/**`:\n- Migrations must be reversible.\n\n\
+             ## Style\nKeep functions short."
+        );
+
+        let src = rules.render_for(&files(&["src/main.rs"])).unwrap();
+        assert!(src.contains("For files matching `src/**/*.rs`, `tests/`:\n- No unwrap"));
+        assert!(!src.contains("Migrations"));
+    }
+
+    #[test]
+    fn test_toml_rules() {
+        let rules = ReviewRules::parse_toml(
+            "[[rules]]\ntext = \"Be strict.\"\n\n[[rules]]\npaths = [\"db/**\"]\ntext = \"Migrations must be reversible.\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules.render_for(&files(&["README.md"])).unwrap(),
+            "Be strict."
+        );
+        assert!(
+            rules
+                .render_for(&files(&["db/x.sql"]))
+                .unwrap()
+                .ends_with("`db/**`:\nMigrations must be reversible.")
+        );
+    }
+
+    #[test]
+    fn test_no_applicable_rules() {
+        let rules = ReviewRules::parse_markdown("## `db/**`\nOnly db.\n").unwrap();
+        assert_eq!(rules.render_for(&files(&["src/main.rs"])), None);
+        assert!(ReviewRules::default().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_glob_is_config_error() {
+        let result = ReviewRules::parse_markdown("## `src/[`\nBroken.\n");
+        assert!(matches!(result, Err(GcopError::Config(msg)) if msg.contains(RULES_MARKDOWN_PATH)));
+    }
+
+    #[test]
+    fn test_load_merges_both_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".gcop")).unwrap();
+        std::fs::write(dir.path().join(RULES_MARKDOWN_PATH), "From markdown.\n").unwrap();
+        std::fs::write(
+            dir.path().join(RULES_TOML_PATH),
+            "[[rules]]\ntext = \"From toml.\"\n",
+        )
+        .unwrap();
+
+        let rules = ReviewRules::load(dir.path()).unwrap();
+        assert_eq!(
+            rules.render_for(&files(&["a.rs"])).unwrap(),
+            "From markdown.\n\nFrom toml."
+        );
+        assert!(
+            ReviewRules::load(&dir.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! 审查结果的交互式分诊
+//!
+//! `review --interactive` 在输出结果后逐个展示问题及其所在 hunk，用户可以确认、
+//! 忽略、标记误报、向模型追问、应用建议的修复，或在编辑器中打开对应行。
+//! 分诊结束后输出会话报告。
+
+use std::sync::Arc;
+
+use colored::Colorize;
+
+use crate::commands::review_fixes::{apply_fix, render_patch};
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::git::GitOperations;
+use crate::git::diff::hunk_at;
+use crate::llm::prompt::build_followup_prompt;
+use crate::llm::{IssueSeverity, LLMProvider, ReviewIssue, ReviewResult};
+use crate::ui::{self, TriageAction};
+
+/// 单个问题的分诊结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriageOutcome {
+    Accepted,
+    Dismissed,
+    FalsePositive,
+    /// 跳过或未处理到
+    Undecided,
+}
+
+impl TriageOutcome {
+    fn label(self) -> &'static str {
+        match self {
+            TriageOutcome::Accepted => "accepted",
+            TriageOutcome::Dismissed => "dismissed",
+            TriageOutcome::FalsePositive => "false positive",
+            TriageOutcome::Undecided => "undecided",
+        }
+    }
+}
+
+/// 一个问题的分诊记录
+#[derive(Debug, Clone)]
+pub struct TriageRecord {
+    /// 在审查结果中的序号（从 1 开始，与文本输出一致）
+    pub number: usize,
+    pub issue: ReviewIssue,
+    pub outcome: TriageOutcome,
+    /// 追问记录 (问题, 回答)
+    pub follow_ups: Vec<(String, String)>,
+    /// 是否已应用建议的修复
+    pub fix_applied: bool,
+}
+
+/// 逐个分诊审查问题
+///
+/// 只处理达到 `review.min_severity` 的问题（与文本输出一致）。`diff` 用于展示问题所在的 hunk。
+pub async fn run(
+    repo: &impl GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    result: &ReviewResult,
+    diff: &str,
+    config: &AppConfig,
+) -> Result<Vec<TriageRecord>> {
+    let colored = config.ui.colored;
+    let min_severity = IssueSeverity::from_config(&config.review.min_severity);
+    let mut records: Vec<TriageRecord> = result
+        .issues
+        .iter()
+        .enumerate()
+        .filter(|(_, issue)| issue.severity.meets(min_severity))
+        .map(|(i, issue)| TriageRecord {
+            number: i + 1,
+            issue: issue.clone(),
+            outcome: TriageOutcome::Undecided,
+            follow_ups: Vec::new(),
+            fix_applied: false,
+        })
+        .collect();
+
+    let total = records.len();
+    'issues: for (position, record) in records.iter_mut().enumerate() {
+        let code = record
+            .issue
+            .file
+            .as_deref()
+            .and_then(|file| hunk_at(diff, file, record.issue.line));
+        print_issue(record, position + 1, total, code.as_deref(), colored);
+
+        loop {
+            let can_fix = record.issue.patch.is_some() && !record.fix_applied;
+            match ui::triage_action_menu(record.issue.file.is_some(), can_fix, colored)? {
+                TriageAction::Accept => record.outcome = TriageOutcome::Accepted,
+                TriageAction::Dismiss => record.outcome = TriageOutcome::Dismissed,
+                TriageAction::FalsePositive => record.outcome = TriageOutcome::FalsePositive,
+                TriageAction::Skip => {}
+                TriageAction::Quit => break 'issues,
+                TriageAction::FollowUp => {
+                    let Some(question) = ui::get_followup_question()? else {
+                        continue;
+                    };
+                    let prompt = build_followup_prompt(
+                        &record.issue,
+                        code.as_deref().unwrap_or("(not available)"),
+                        &record.follow_ups,
+                        &question,
+                    );
+                    let spinner = ui::Spinner::new("Asking the model...");
+                    let answer = provider.complete(&prompt, Some(&spinner)).await;
+                    spinner.finish_and_clear();
+                    match answer {
+                        Ok(answer) => {
+                            println!("\n{}\n", answer.trim());
+                            record
+                                .follow_ups
+                                .push((question, answer.trim().to_string()));
+                        }
+                        Err(e) => ui::error(&format!("Follow-up failed: {}", e), colored),
+                    }
+                    continue;
+                }
+                TriageAction::ApplyFix => {
+                    let Some(patch) = record.issue.patch.as_deref() else {
+                        continue;
+                    };
+                    match apply_fix(repo, patch) {
+                        Ok(()) => {
+                            record.fix_applied = true;
+                            record.outcome = TriageOutcome::Accepted;
+                            ui::success("Fix applied", colored);
+                        }
+                        Err(e) => {
+                            ui::error(&format!("Fix no longer applies cleanly: {}", e), colored);
+                            continue;
+                        }
+                    }
+                }
+                TriageAction::OpenEditor => {
+                    if let Some(file) = record.issue.file.as_deref()
+                        && let Err(e) = ui::open_at_line(file, record.issue.line)
+                    {
+                        ui::error(&e.to_string(), colored);
+                    }
+                    continue;
+                }
+            }
+            break;
+        }
+    }
+
+    Ok(records)
+}
+
+/// 展示一个问题及其 hunk
+fn print_issue(
+    record: &TriageRecord,
+    position: usize,
+    total: usize,
+    code: Option<&str>,
+    colored: bool,
+) {
+    println!();
+    let title = format!(
+        "Issue {}/{} (#{}) [{:?}]",
+        position, total, record.number, record.issue.severity
+    )
+    .to_uppercase();
+    if colored {
+        println!("{}", title.cyan().bold());
+    } else {
+        println!("{}", title);
+    }
+    println!("{}", record.issue.description);
+    if let Some(location) = location(&record.issue) {
+        println!("Location: {}", location);
+    }
+    if let Some(code) = code {
+        println!();
+        print!("{}", colorize_hunk(code, colored));
+    }
+    if let Some(patch) = &record.issue.patch {
+        println!("\nSuggested fix:");
+        print!("{}", render_patch(patch, "  ", colored));
+    }
+    println!();
+}
+
+/// `file:line` 形式的位置
+fn location(issue: &ReviewIssue) -> Option<String> {
+    let file = issue.file.as_deref()?;
+    Some(match issue.line {
+        Some(line) => format!("{}:{}", file, line),
+        None => file.to_string(),
+    })
+}
+
+/// 给标注了行号的 hunk 上色：新增行绿色、删除行红色、文件头和 hunk 头灰色
+fn colorize_hunk(code: &str, colored: bool) -> String {
+    if !colored {
+        return code.to_string();
+    }
+    let mut out = String::with_capacity(code.len());
+    for line in code.lines() {
+        // 行号前缀为 6 个字符，之后才是 diff 的 +/- 标记
+        let marker = line.get(6..7);
+        let styled = if line.starts_with("diff --git")
+            || line.starts_with("--- ")
+            || line.starts_with("+++ ")
+            || line.starts_with("@@")
+        {
+            line.bright_black().to_string()
+        } else if marker == Some("+") {
+            line.green().to_string()
+        } else if marker == Some("-") {
+            line.red().to_string()
+        } else {
+            line.to_string()
+        };
+        out.push_str(&styled);
+        out.push('\n');
+    }
+    out
+}
+
+/// 渲染分诊会话报告
+pub fn render_report(records: &[TriageRecord], colored: bool) -> String {
+    let mut out = String::new();
+    let count = |outcome| records.iter().filter(|r| r.outcome == outcome).count();
+    out.push_str(&format!("{}\n\n", ui::info("Triage report", colored)));
+    out.push_str(&format!(
+        "  {} accepted, {} dismissed, {} false positive, {} undecided\n\n",
+        count(TriageOutcome::Accepted),
+        count(TriageOutcome::Dismissed),
+        count(TriageOutcome::FalsePositive),
+        count(TriageOutcome::Undecided),
+    ));
+
+    for record in records {
+        out.push_str(&format!(
+            "  {}. [{}] {}\n",
+            record.number,
+            record.outcome.label(),
+            record.issue.description
+        ));
+        if let Some(location) = location(&record.issue) {
+            out.push_str(&format!("     Location: {}\n", location));
+        }
+        if record.fix_applied {
+            out.push_str("     Fix applied\n");
+        }
+        if !record.follow_ups.is_empty() {
+            out.push_str(&format!("     Follow-ups: {}\n", record.follow_ups.len()));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn record(number: usize, outcome: TriageOutcome, file: Option<&str>) -> TriageRecord {
+        TriageRecord {
+            number,
+            issue: ReviewIssue {
+                severity: IssueSeverity::Warning,
+                description: format!("Issue {}", number),
+                file: file.map(String::from),
+                line: file.map(|_| 3),
+                patch: None,
+            },
+            outcome,
+            follow_ups: Vec::new(),
+            fix_applied: false,
+        }
+    }
+
+    #[test]
+    fn test_render_report() {
+        let mut asked = record(2, TriageOutcome::FalsePositive, None);
+        asked
+            .follow_ups
+            .push(("Why?".to_string(), "Never mind.".to_string()));
+        let mut fixed = record(1, TriageOutcome::Accepted, Some("src/a.rs"));
+        fixed.fix_applied = true;
+        let records = vec![fixed, asked, record(4, TriageOutcome::Undecided, None)];
+
+        assert_eq!(
+            render_report(&records, false),
+            "ℹ Triage report\n\n\
+             \x20 1 accepted, 0 dismissed, 1 false positive, 1 undecided\n\n\
+             \x20 1. [accepted] Issue 1\n\
+             \x20    Location: src/a.rs:3\n\
+             \x20    Fix applied\n\
+             \x20 2. [false positive] Issue 2\n\
+             \x20    Follow-ups: 1\n\
+             \x20 4. [undecided] Issue 4\n"
+        );
+    }
+
+    #[test]
+    fn test_colorize_hunk_plain() {
+        let code = "@@ -1 +1 @@\n      -old\n    1 +new\n";
+        assert_eq!(colorize_hunk(code, false), code);
+    }
+}
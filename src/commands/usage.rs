@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, Local};
+use serde::Serialize;
+
+use crate::config::{AppConfig, ModelPrice};
+use crate::error::Result;
+use crate::llm::usage::{UsageLog, UsageRecord};
+use crate::ui;
+
+/// 按 provider / 模型汇总的用量
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelUsage {
+    pub provider: String,
+    pub model: String,
+    pub calls: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// 估算费用（USD），未配置价格时为 None
+    pub cost: Option<f64>,
+}
+
+/// 单日用量
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DailyUsage {
+    pub calls: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// 用量报告
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageReport {
+    pub days: i64,
+    pub total_calls: usize,
+    pub total_input_tokens: u64,
+    pub total_output_tokens: u64,
+    /// 所有已配置价格模型的费用合计
+    pub total_cost: Option<f64>,
+    pub models: Vec<ModelUsage>,
+    pub by_day: BTreeMap<String, DailyUsage>,
+}
+
+impl UsageReport {
+    /// 从用量记录计算最近 `days` 天的报告
+    pub fn from_records(
+        records: &[UsageRecord],
+        days: i64,
+        prices: &HashMap<String, ModelPrice>,
+        now: DateTime<Local>,
+    ) -> Self {
+        let since = now - Duration::days(days);
+        let filtered: Vec<&UsageRecord> = records.iter().filter(|r| r.timestamp >= since).collect();
+
+        let mut model_map: HashMap<(String, String), ModelUsage> = HashMap::new();
+        let mut by_day: BTreeMap<String, DailyUsage> = BTreeMap::new();
+
+        for record in &filtered {
+            let entry = model_map
+                .entry((record.provider.clone(), record.model.clone()))
+                .or_insert_with(|| ModelUsage {
+                    provider: record.provider.clone(),
+                    model: record.model.clone(),
+                    calls: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cost: None,
+                });
+            entry.calls += 1;
+            entry.input_tokens += record.input_tokens;
+            entry.output_tokens += record.output_tokens;
+
+            let day = by_day
+                .entry(record.timestamp.format("%Y-%m-%d").to_string())
+                .or_default();
+            day.calls += 1;
+            day.input_tokens += record.input_tokens;
+            day.output_tokens += record.output_tokens;
+        }
+
+        let mut models: Vec<ModelUsage> = model_map
+            .into_values()
+            .map(|mut m| {
+                m.cost = prices
+                    .get(&m.model)
+                    .map(|price| estimate_cost(m.input_tokens, m.output_tokens, price));
+                m
+            })
+            .collect();
+        models.sort_by_key(|m| std::cmp::Reverse(m.input_tokens + m.output_tokens));
+
+        let costs: Vec<f64> = models.iter().filter_map(|m| m.cost).collect();
+        let total_cost = if costs.is_empty() {
+            None
+        } else {
+            Some(costs.iter().sum())
+        };
+
+        Self {
+            days,
+            total_calls: filtered.len(),
+            total_input_tokens: filtered.iter().map(|r| r.input_tokens).sum(),
+            total_output_tokens: filtered.iter().map(|r| r.output_tokens).sum(),
+            total_cost,
+            models,
+            by_day,
+        }
+    }
+}
+
+/// 按每百万 token 单价估算费用
+fn estimate_cost(input_tokens: u64, output_tokens: u64, price: &ModelPrice) -> f64 {
+    (input_tokens as f64 / 1_000_000.0) * price.input
+        + (output_tokens as f64 / 1_000_000.0) * price.output
+}
+
+/// 运行 usage 命令
+pub fn run(days: i64, format: &str, config: &AppConfig) -> Result<()> {
+    let colored = config.ui.colored;
+    let log = UsageLog::default_location()?;
+    let records = log.read_all()?;
+
+    if records.is_empty() {
+        ui::warning(
+            &format!("No usage recorded yet ({}).", log.path().display()),
+            colored,
+        );
+        return Ok(());
+    }
+
+    let report = UsageReport::from_records(&records, days, &config.usage.prices, Local::now());
+
+    match format {
+        "json" => output_json(&report)?,
+        _ => output_text(&report, colored),
+    }
+
+    Ok(())
+}
+
+/// 文本格式输出
+fn output_text(report: &UsageReport, colored: bool) {
+    println!();
+    println!(
+        "{}",
+        ui::info(&format!("Token Usage (last {} days)", report.days), colored)
+    );
+    println!("{}", "=".repeat(40));
+
+    println!();
+    ui::step("", "Overview", colored);
+    println!("  Calls:          {}", report.total_calls);
+    println!("  Input tokens:   {}", report.total_input_tokens);
+    println!("  Output tokens:  {}", report.total_output_tokens);
+    if let Some(cost) = report.total_cost {
+        println!("  Estimated cost: ${:.4}", cost);
+    }
+
+    if !report.models.is_empty() {
+        println!();
+        ui::step("", "By Provider / Model", colored);
+        for m in &report.models {
+            let cost = m.cost.map(|c| format!("  ${:.4}", c)).unwrap_or_default();
+            println!(
+                "  {} / {}  {} calls, {} in, {} out{}",
+                m.provider, m.model, m.calls, m.input_tokens, m.output_tokens, cost
+            );
+        }
+    }
+
+    if !report.by_day.is_empty() {
+        println!();
+        ui::step("", "By Day", colored);
+        for (day, usage) in report.by_day.iter().rev() {
+            println!(
+                "  {}: {} calls, {} in, {} out",
+                day, usage.calls, usage.input_tokens, usage.output_tokens
+            );
+        }
+    }
+
+    println!();
+}
+
+/// JSON 格式输出
+fn output_json(report: &UsageReport) -> Result<()> {
+    let json = serde_json::to_string_pretty(report)?;
+    println!("{}", json);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn record(
+        provider: &str,
+        model: &str,
+        days_ago: i64,
+        input: u64,
+        output: u64,
+        now: DateTime<Local>,
+    ) -> UsageRecord {
+        UsageRecord {
+            timestamp: now - Duration::days(days_ago),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+        }
+    }
+
+    #[test]
+    fn test_report_groups_by_model_and_day() {
+        let now = Local::now();
+        let records = vec![
+            record("claude", "sonnet", 0, 1000, 100, now),
+            record("claude", "sonnet", 1, 2000, 200, now),
+            record("openai", "gpt-4o", 0, 500, 50, now),
+        ];
+
+        let report = UsageReport::from_records(&records, 30, &HashMap::new(), now);
+
+        assert_eq!(report.total_calls, 3);
+        assert_eq!(report.total_input_tokens, 3500);
+        assert_eq!(report.total_output_tokens, 350);
+        assert_eq!(report.models.len(), 2);
+        assert_eq!(report.models[0].model, "sonnet");
+        assert_eq!(report.models[0].calls, 2);
+        assert_eq!(report.by_day.len(), 2);
+        assert_eq!(report.total_cost, None);
+    }
+
+    #[test]
+    fn test_report_filters_by_days() {
+        let now = Local::now();
+        let records = vec![
+            record("claude", "sonnet", 0, 10, 1, now),
+            record("claude", "sonnet", 40, 10, 1, now),
+        ];
+
+        let report = UsageReport::from_records(&records, 30, &HashMap::new(), now);
+        assert_eq!(report.total_calls, 1);
+    }
+
+    #[test]
+    fn test_report_estimates_cost_with_prices() {
+        let now = Local::now();
+        let records = vec![
+            record("claude", "sonnet", 0, 1_000_000, 500_000, now),
+            record("ollama", "llama3", 0, 1000, 1000, now),
+        ];
+        let mut prices = HashMap::new();
+        prices.insert(
+            "sonnet".to_string(),
+            ModelPrice {
+                input: 3.0,
+                output: 15.0,
+            },
+        );
+
+        let report = UsageReport::from_records(&records, 30, &prices, now);
+
+        let sonnet = report.models.iter().find(|m| m.model == "sonnet").unwrap();
+        assert_eq!(sonnet.cost, Some(10.5));
+        let llama = report.models.iter().find(|m| m.model == "llama3").unwrap();
+        assert_eq!(llama.cost, None);
+        assert_eq!(report.total_cost, Some(10.5));
+    }
+}
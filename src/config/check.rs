@@ -0,0 +1,443 @@
+//! 配置 schema 校验
+//!
+//! serde 只检查类型：未知键会被忽略（`ProviderConfig` 的 `extra` 更是接收任意键），
+//! 枚举和数值范围也不检查，拼错的键或非法取值会静默回退到默认行为。
+//! 这里按 schema 检查配置文件中的未知键（附带 "did you mean" 建议）、枚举取值和数值范围，
+//! 并给出所在行号。
+
+use std::fmt;
+
+use toml_edit::{Document, Item, TableLike};
+
+use super::AppConfig;
+use super::sources::join_key;
+
+/// 配置结构中的一个节点
+enum Node {
+    /// 键固定的表
+    Table(&'static [(&'static str, Node)]),
+    /// 键由用户命名的表（如 providers），每一项结构相同
+    Map(&'static Node),
+    /// profile：匹配规则加上任意配置节
+    Profile,
+    /// 叶子值
+    Value(Rule),
+}
+
+/// 叶子值的取值规则（类型由 serde 检查，这里只检查取值）
+enum Rule {
+    Any,
+    OneOf(&'static [&'static str]),
+    /// 闭区间，`max` 为 None 表示无上限
+    Range {
+        min: f64,
+        max: Option<f64>,
+    },
+    /// 大于 0
+    Positive,
+}
+
+const ANY: Node = Node::Value(Rule::Any);
+const POSITIVE: Node = Node::Value(Rule::Positive);
+const NON_NEGATIVE: Node = Node::Value(Rule::Range {
+    min: 0.0,
+    max: None,
+});
+const SEVERITY: Node = Node::Value(Rule::OneOf(&["critical", "warning", "info"]));
+
+const PROVIDER: Node = Node::Table(&[
+    (
+        "api_style",
+        Node::Value(Rule::OneOf(&["claude", "openai", "ollama"])),
+    ),
+    ("endpoint", ANY),
+    ("api_key", ANY),
+    ("api_key_file", ANY),
+    ("api_key_cmd", ANY),
+    ("model", ANY),
+    ("max_tokens", POSITIVE),
+    (
+        "temperature",
+        Node::Value(Rule::Range {
+            min: 0.0,
+            max: Some(2.0),
+        }),
+    ),
+    ("context_window", POSITIVE),
+]);
+
+const PRICE: Node = Node::Table(&[("input", NON_NEGATIVE), ("output", NON_NEGATIVE)]);
+
+/// 顶层配置节
+const SECTIONS: &[(&str, Node)] = &[
+    (
+        "llm",
+        Node::Table(&[
+            ("default_provider", ANY),
+            ("providers", Node::Map(&PROVIDER)),
+        ]),
+    ),
+    (
+        "commit",
+        Node::Table(&[
+            ("show_diff_preview", ANY),
+            ("allow_edit", ANY),
+            ("confirm_before_commit", ANY),
+            ("custom_prompt", ANY),
+            ("max_retries", ANY),
+        ]),
+    ),
+    (
+        "review",
+        Node::Table(&[
+            ("show_full_diff", ANY),
+            ("min_severity", SEVERITY),
+            ("pre_push_severity", SEVERITY),
+            ("custom_prompt", ANY),
+        ]),
+    ),
+    (
+        "ui",
+        Node::Table(&[("colored", ANY), ("verbose", ANY), ("streaming", ANY)]),
+    ),
+    (
+        "network",
+        Node::Table(&[
+            ("request_timeout", POSITIVE),
+            ("connect_timeout", POSITIVE),
+            ("max_retries", ANY),
+            ("retry_delay_ms", ANY),
+        ]),
+    ),
+    ("file", Node::Table(&[("max_size", POSITIVE)])),
+    (
+        "security",
+        Node::Table(&[
+            (
+                "secret_scan",
+                Node::Value(Rule::OneOf(&["block", "redact", "confirm", "off"])),
+            ),
+            ("entropy_threshold", NON_NEGATIVE),
+            ("allow_patterns", ANY),
+            ("exclude_paths", ANY),
+            ("trusted_repos", ANY),
+        ]),
+    ),
+    ("cache", Node::Table(&[("enabled", ANY), ("max_size", ANY)])),
+    (
+        "usage",
+        Node::Table(&[("enabled", ANY), ("prices", Node::Map(&PRICE))]),
+    ),
+    (
+        "budget",
+        Node::Table(&[
+            ("max_request_tokens", ANY),
+            ("on_exceed", Node::Value(Rule::OneOf(&["warn", "refuse"]))),
+        ]),
+    ),
+    (
+        "convention",
+        Node::Table(&[
+            ("types", ANY),
+            ("scopes", ANY),
+            ("require_scope", ANY),
+            ("max_subject_length", POSITIVE),
+            ("suggest_rewrite", ANY),
+        ]),
+    ),
+    ("profiles", Node::Map(&Node::Profile)),
+];
+
+/// profile 中除配置节以外的键
+const PROFILE_KEYS: &[(&str, Node)] = &[("match_remote", ANY), ("match_path", ANY)];
+
+/// 未知键建议的最低相似度（Jaro-Winkler）
+const SUGGESTION_THRESHOLD: f64 = 0.8;
+
+/// 一个校验问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    /// 所在行号（从 1 开始），来自合并后的配置时为 None
+    pub line: Option<usize>,
+    /// 点分路径
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}: {}", line, self.key, self.message),
+            None => write!(f, "{}: {}", self.key, self.message),
+        }
+    }
+}
+
+/// 检查配置文件内容：未知键、枚举取值和数值范围
+///
+/// TOML 语法错误不在这里报告，留给后续解析
+pub fn check_document(content: &str) -> Vec<ConfigIssue> {
+    let checker = Checker {
+        content: Some(content),
+        unknown_keys: true,
+        issues: Vec::new(),
+    };
+    checker.run(content)
+}
+
+/// 检查合并后的配置取值（如来自环境变量的值），不检查未知键
+pub fn check_config(config: &AppConfig) -> Vec<ConfigIssue> {
+    let Ok(content) = toml::to_string(config) else {
+        return Vec::new();
+    };
+    let checker = Checker {
+        content: None,
+        unknown_keys: false,
+        issues: Vec::new(),
+    };
+    checker.run(&content)
+}
+
+struct Checker<'a> {
+    /// 原始内容，用于把位置换算成行号
+    content: Option<&'a str>,
+    unknown_keys: bool,
+    issues: Vec<ConfigIssue>,
+}
+
+impl Checker<'_> {
+    fn run(mut self, content: &str) -> Vec<ConfigIssue> {
+        if let Ok(doc) = Document::parse(content)
+            && let Some(table) = doc.as_item().as_table_like()
+        {
+            self.check_table(table, &field_refs(SECTIONS), "");
+        }
+        self.issues
+    }
+
+    fn check_table(&mut self, table: &dyn TableLike, fields: &[(&str, &Node)], prefix: &str) {
+        for (key, item) in table.iter() {
+            let path = join_key(prefix, key);
+            match fields.iter().find(|(name, _)| *name == key) {
+                Some((_, node)) => self.check_item(item, node, &path),
+                None if self.unknown_keys => {
+                    let span = table.get_key_value(key).and_then(|(k, _)| k.span());
+                    let names = fields.iter().map(|(name, _)| *name);
+                    self.report(span, path, unknown_key_message(key, names));
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn check_item(&mut self, item: &Item, node: &Node, path: &str) {
+        match node {
+            Node::Table(fields) => {
+                if let Some(table) = item.as_table_like() {
+                    self.check_table(table, &field_refs(fields), path);
+                }
+            }
+            Node::Map(child) => {
+                if let Some(table) = item.as_table_like() {
+                    for (key, item) in table.iter() {
+                        self.check_item(item, child, &join_key(path, key));
+                    }
+                }
+            }
+            Node::Profile => {
+                // profile 中可以出现匹配规则和除 `profiles` 以外的任意配置节
+                if let Some(table) = item.as_table_like() {
+                    let sections = SECTIONS.iter().filter(|(name, _)| *name != "profiles");
+                    let fields: Vec<(&str, &Node)> = PROFILE_KEYS
+                        .iter()
+                        .chain(sections)
+                        .map(|(name, node)| (*name, node))
+                        .collect();
+                    self.check_table(table, &fields, path);
+                }
+            }
+            Node::Value(rule) => {
+                if let Some(message) = rule.check(item) {
+                    self.report(item.span(), path.to_string(), message);
+                }
+            }
+        }
+    }
+
+    fn report(&mut self, span: Option<std::ops::Range<usize>>, key: String, message: String) {
+        let line = match (self.content, span) {
+            (Some(content), Some(span)) => content
+                .get(..span.start)
+                .map(|before| before.matches('\n').count() + 1),
+            _ => None,
+        };
+        self.issues.push(ConfigIssue { line, key, message });
+    }
+}
+
+impl Rule {
+    /// 取值不符合规则时返回说明；类型不符时忽略，由 serde 报告
+    fn check(&self, item: &Item) -> Option<String> {
+        let value = item.as_value()?;
+        match self {
+            Rule::Any => None,
+            Rule::OneOf(allowed) => {
+                let actual = value.as_str()?;
+                if allowed.contains(&actual) {
+                    return None;
+                }
+                let quoted: Vec<String> = allowed.iter().map(|v| format!("\"{}\"", v)).collect();
+                let hint = suggest(actual, allowed.iter().copied())
+                    .map(|s| format!(", did you mean \"{}\"?", s))
+                    .unwrap_or_default();
+                Some(format!(
+                    "must be one of {} (got \"{}\"{})",
+                    quoted.join(", "),
+                    actual,
+                    hint
+                ))
+            }
+            Rule::Range { min, max } => {
+                let actual = number(value)?;
+                let in_range = actual >= *min && max.is_none_or(|max| actual <= max);
+                match max {
+                    _ if in_range => None,
+                    Some(max) => Some(format!(
+                        "must be between {} and {} (got {})",
+                        min, max, actual
+                    )),
+                    None => Some(format!("must be at least {} (got {})", min, actual)),
+                }
+            }
+            Rule::Positive => {
+                let actual = number(value)?;
+                (actual <= 0.0).then(|| format!("must be greater than 0 (got {})", actual))
+            }
+        }
+    }
+}
+
+fn field_refs<'a>(fields: &'a [(&'a str, Node)]) -> Vec<(&'a str, &'a Node)> {
+    fields.iter().map(|(name, node)| (*name, node)).collect()
+}
+
+fn number(value: &toml_edit::Value) -> Option<f64> {
+    value
+        .as_float()
+        .or_else(|| value.as_integer().map(|v| v as f64))
+}
+
+fn unknown_key_message<'a>(key: &str, candidates: impl Iterator<Item = &'a str>) -> String {
+    match suggest(key, candidates) {
+        Some(suggestion) => format!("unknown key (did you mean '{}'?)", suggestion),
+        None => "unknown key".to_string(),
+    }
+}
+
+/// 找出与 `input` 最相近的候选
+fn suggest<'a>(input: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (strsim::jaro_winkler(input, candidate), candidate))
+        .filter(|(score, _)| *score >= SUGGESTION_THRESHOLD)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn messages(content: &str) -> Vec<String> {
+        check_document(content)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_config_has_no_issues() {
+        let content = r#"
+[llm]
+default_provider = "claude"
+
+[llm.providers.claude]
+api_key = "sk-ant"
+model = "claude-sonnet"
+temperature = 0.3
+context_window = 200000
+
+[review]
+min_severity = "warning"
+
+[usage.prices]
+"gpt-4.1" = { input = 2.0, output = 8.0 }
+
+[profiles.work]
+match_remote = ["*acme*"]
+[profiles.work.llm]
+default_provider = "openai"
+"#;
+        assert_eq!(messages(content), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_unknown_keys_with_suggestions() {
+        let content = "[llm.providers.claude]\nmodel = \"m\"\ntemprature = 0.5\nmax_token = 100\n\n[comit]\nfoo = 1\n";
+        assert_eq!(
+            messages(content),
+            vec![
+                "line 3: llm.providers.claude.temprature: unknown key (did you mean 'temperature'?)",
+                "line 4: llm.providers.claude.max_token: unknown key (did you mean 'max_tokens'?)",
+                "line 6: comit: unknown key (did you mean 'commit'?)",
+            ]
+        );
+
+        // 差异太大时不给建议
+        assert_eq!(
+            messages("[ui]\nshiny = true\n"),
+            vec!["line 2: ui.shiny: unknown key"]
+        );
+    }
+
+    #[test]
+    fn test_enum_and_range_values() {
+        let content = "[review]\nmin_severity = \"high\"\n\n[network]\nrequest_timeout = 0\n\n[llm.providers.openai]\nmodel = \"m\"\ntemperature = 2.5\n";
+        assert_eq!(
+            messages(content),
+            vec![
+                "line 2: review.min_severity: must be one of \"critical\", \"warning\", \"info\" (got \"high\")",
+                "line 5: network.request_timeout: must be greater than 0 (got 0)",
+                "line 9: llm.providers.openai.temperature: must be between 0 and 2 (got 2.5)",
+            ]
+        );
+
+        let issues = check_document("[security]\nsecret_scan = \"redcat\"\n");
+        assert!(issues[0].message.contains("did you mean \"redact\"?"));
+    }
+
+    #[test]
+    fn test_profile_sections_are_checked() {
+        let content = "[profiles.work]\nmatch_path = [\"~/work/**\"]\n[profiles.work.review]\nmin_severity = \"high\"\n[profiles.work.covention]\ntypes = []\n";
+        assert_eq!(
+            messages(content),
+            vec![
+                "line 4: profiles.work.review.min_severity: must be one of \"critical\", \"warning\", \"info\" (got \"high\")",
+                "line 5: profiles.work.covention: unknown key (did you mean 'convention'?)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_config_reports_values_only() {
+        let mut config = AppConfig::default();
+        config.budget.on_exceed = "block".to_string();
+        let issues = check_config(&config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, None);
+        assert_eq!(
+            issues[0].to_string(),
+            "budget.on_exceed: must be one of \"warn\", \"refuse\" (got \"block\")"
+        );
+    }
+}
@@ -1,20 +1,52 @@
+pub mod check;
+pub mod profile;
+pub mod repo;
 pub mod schema;
+pub mod sources;
 
-use config::{Config, Environment, File};
+use config::{Config, Environment, File, FileFormat, Source};
 use directories::ProjectDirs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::error::Result;
+use crate::error::{GcopError, Result};
+use crate::ui;
 pub use schema::*;
+pub use sources::{ConfigLayer, LoadedConfig};
 
 /// 加载应用配置
 ///
 /// 配置加载优先级（从高到低）：
 /// 1. 环境变量（GCOP_* 前缀）
-/// 2. 配置文件（~/.config/gcop/config.toml）
-/// 3. 默认值
-pub fn load_config() -> Result<AppConfig> {
+/// 2. 仓库配置（从工作目录向上到 git 根目录找到的 `.gcop.toml`）
+/// 3. 选中的 profile（`profile` 参数、`GCOP_PROFILE` 或按 remote / 路径自动匹配）
+/// 4. 用户配置文件（~/.config/gcop/config.toml）
+/// 5. 默认值
+pub fn load_config(profile: Option<&str>) -> Result<AppConfig> {
+    load_config_with_sources(profile).map(|loaded| loaded.config)
+}
+
+/// 加载应用配置，同时返回各层设置了哪些键（用于 `config show`）
+pub fn load_config_with_sources(profile: Option<&str>) -> Result<LoadedConfig> {
+    let cwd = std::env::current_dir().ok();
+    let requested = profile
+        .map(String::from)
+        .or_else(|| std::env::var(profile::PROFILE_ENV).ok())
+        .filter(|name| !name.is_empty());
+    load_config_from(
+        get_config_path().as_deref(),
+        cwd.as_deref(),
+        requested.as_deref(),
+    )
+}
+
+/// 从指定的用户配置文件和工作目录加载配置
+fn load_config_from(
+    user_config: Option<&Path>,
+    cwd: Option<&Path>,
+    requested_profile: Option<&str>,
+) -> Result<LoadedConfig> {
     let mut builder = Config::builder();
+    let mut layers = Vec::new();
 
     // 1. 设置默认值
     builder = builder
@@ -25,33 +57,204 @@ pub fn load_config() -> Result<AppConfig> {
         .set_default("commit.max_retries", 10)?
         .set_default("review.show_full_diff", true)?
         .set_default("review.min_severity", "info")?
+        .set_default("review.pre_push_severity", "critical")?
         .set_default("ui.colored", true)?
         .set_default("ui.verbose", false)?
         .set_default("network.request_timeout", 120)?
         .set_default("network.connect_timeout", 10)?
         .set_default("network.max_retries", 3)?
         .set_default("network.retry_delay_ms", 1000)?
-        .set_default("file.max_size", 10 * 1024 * 1024)?;
-
-    // 2. 加载配置文件（如果存在）
-    if let Some(config_path) = get_config_path()
-        && config_path.exists()
-    {
+        .set_default("file.max_size", 10 * 1024 * 1024)?
+        .set_default("security.secret_scan", "confirm")?
+        .set_default("security.entropy_threshold", 4.5)?
+        .set_default("cache.enabled", true)?
+        .set_default("cache.max_size", 50 * 1024 * 1024)?
+        .set_default("usage.enabled", true)?
+        .set_default("budget.max_request_tokens", 0)?
+        .set_default("budget.on_exceed", "refuse")?
+        .set_default("convention.max_subject_length", 72)?
+        .set_default("convention.require_scope", false)?
+        .set_default("convention.suggest_rewrite", false)?;
+
+    // 2. 加载用户配置文件（如果存在）
+    let user_config = user_config.filter(|path| path.exists());
+    let user_content = user_config.and_then(|path| std::fs::read_to_string(path).ok());
+    if let (Some(path), Some(content)) = (user_config, &user_content) {
+        ensure_valid(&path.display().to_string(), check::check_document(content))?;
+    }
+    // 格式错误时留给下面的 config 构建报告
+    let user_table: toml::Table = user_content
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default();
+    if let Some(config_path) = user_config {
         builder = builder.add_source(File::from(config_path));
+        layers.push(ConfigLayer::from_table("config.toml", &user_table));
+    }
+
+    // 3. 叠加选中的 profile
+    let profiles = match user_table.get("profiles") {
+        Some(toml::Value::Table(profiles)) => profiles.clone(),
+        _ => toml::Table::new(),
+    };
+    let remotes = cwd.map(profile::remote_urls).unwrap_or_default();
+    let selected = profile::select(&profiles, requested_profile, &remotes, cwd)?;
+    if let Some(selected) = &selected {
+        tracing::debug!(
+            "Using config profile '{}' ({})",
+            selected.name,
+            selected.reason
+        );
+        builder = builder.add_source(File::from_str(
+            &toml::to_string(&selected.overrides).map_err(|e| {
+                GcopError::Config(format!("Invalid profiles.{}: {}", selected.name, e))
+            })?,
+            FileFormat::Toml,
+        ));
+        layers.push(ConfigLayer::from_table(
+            format!("profile {}", selected.name),
+            &selected.overrides,
+        ));
     }
 
-    // 3. 加载环境变量（GCOP_ 前缀，优先级最高）
-    builder = builder.add_source(
-        Environment::with_prefix("GCOP")
-            .separator("_")
-            .try_parsing(true),
-    );
+    // 4. 加载仓库配置（未受信任的仓库只保留白名单中的键）
+    // 仓库只能追加 security.exclude_paths，不能去掉用户（或 profile）设置的排除规则
+    let mut user_exclude_paths = None;
+    if let Some(found) = cwd.and_then(repo::find_repo_config) {
+        user_exclude_paths = Some(
+            selected
+                .as_ref()
+                .and_then(|selected| exclude_paths_of(&selected.overrides))
+                .or_else(|| exclude_paths_of(&user_table))
+                .unwrap_or_default(),
+        );
+        let trusted_repos: Vec<String> = user_table
+            .get("security")
+            .and_then(|security| security.get("trusted_repos"))
+            .and_then(|repos| repos.as_array())
+            .map(|repos| {
+                repos
+                    .iter()
+                    .filter_map(|repo| repo.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let colored = user_table
+            .get("ui")
+            .and_then(|ui| ui.get("colored"))
+            .and_then(|colored| colored.as_bool())
+            .unwrap_or(true);
+        let content = read_repo_config(&found, &trusted_repos, colored)?;
+        builder = builder.add_source(File::from_str(&content, FileFormat::Toml));
+        // read_repo_config 已校验过格式
+        let table: toml::Table = toml::from_str(&content).unwrap_or_default();
+        layers.push(ConfigLayer::from_table(repo::REPO_CONFIG_FILE, &table));
+    }
+
+    // 5. 加载环境变量（GCOP_ 前缀，优先级最高）
+    let environment = Environment::with_prefix("GCOP")
+        .separator("_")
+        .try_parsing(true);
+    for key in environment.collect()?.into_keys() {
+        let var = format!("GCOP_{}", key.replace('.', "_").to_uppercase());
+        layers.push(ConfigLayer {
+            label: format!("env {}", var),
+            keys: [key].into(),
+        });
+    }
+    builder = builder.add_source(environment);
 
     // 构建并反序列化配置
     let config = builder.build()?;
-    let app_config: AppConfig = config.try_deserialize()?;
+    let mut app_config: AppConfig = config.try_deserialize()?;
+    app_config.active_profile = selected.map(|selected| selected.name);
+    if let Some(mut exclude_paths) = user_exclude_paths {
+        for path in std::mem::take(&mut app_config.security.exclude_paths) {
+            if !exclude_paths.contains(&path) {
+                exclude_paths.push(path);
+            }
+        }
+        app_config.security.exclude_paths = exclude_paths;
+    }
+
+    let loaded = LoadedConfig {
+        config: app_config,
+        layers,
+    };
+    // 文件已逐个检查过，这里主要检查环境变量带来的取值
+    let issues = check::check_config(&loaded.config);
+    if !issues.is_empty() {
+        let lines: Vec<String> = issues
+            .iter()
+            .map(|issue| format!("  {} ({})", issue, loaded.source_of(&issue.key)))
+            .collect();
+        return Err(GcopError::Config(format!(
+            "Invalid configuration:\n{}",
+            lines.join("\n")
+        )));
+    }
+    Ok(loaded)
+}
+
+/// 配置表中的 `security.exclude_paths`，未设置时返回 None
+fn exclude_paths_of(table: &toml::Table) -> Option<Vec<String>> {
+    let paths = table.get("security")?.get("exclude_paths")?.as_array()?;
+    Some(
+        paths
+            .iter()
+            .filter_map(|path| path.as_str().map(String::from))
+            .collect(),
+    )
+}
+
+/// 配置文件存在校验问题时返回错误，列出每个问题及其行号
+fn ensure_valid(file: &str, issues: Vec<check::ConfigIssue>) -> Result<()> {
+    if issues.is_empty() {
+        return Ok(());
+    }
+    let lines: Vec<String> = issues.iter().map(|issue| format!("  {}", issue)).collect();
+    Err(GcopError::Config(format!(
+        "Invalid configuration in {}:\n{}",
+        file,
+        lines.join("\n")
+    )))
+}
+
+/// 读取仓库配置，未受信任时移除白名单以外的键并给出警告
+fn read_repo_config(
+    found: &repo::RepoConfigFile,
+    trusted_repos: &[String],
+    colored: bool,
+) -> Result<String> {
+    let content = std::fs::read_to_string(&found.path)?;
+    let mut table: toml::Table = toml::from_str(&content)
+        .map_err(|e| GcopError::Config(format!("Invalid {}: {}", found.path.display(), e)))?;
+    ensure_valid(
+        &found.path.display().to_string(),
+        check::check_document(&content),
+    )?;
+    if repo::is_trusted(&found.root, trusted_repos) {
+        return Ok(content);
+    }
 
-    Ok(app_config)
+    let ignored = repo::filter_untrusted(&mut table);
+    // 同一进程可能多次加载配置，只警告一次
+    static WARN_ONCE: std::sync::Once = std::sync::Once::new();
+    if !ignored.is_empty() {
+        WARN_ONCE.call_once(|| {
+            ui::warning_stderr(
+                &format!(
+                    "Ignoring {} in {}: the repository is not trusted. \
+                     Add {} to security.trusted_repos in your config to allow it.",
+                    ignored.join(", "),
+                    found.path.display(),
+                    found.root.display()
+                ),
+                colored,
+            );
+        });
+    }
+    toml::to_string(&table)
+        .map_err(|e| GcopError::Config(format!("Invalid {}: {}", found.path.display(), e)))
 }
 
 /// 获取配置文件路径
@@ -68,6 +271,38 @@ pub fn get_config_dir() -> Option<PathBuf> {
     ProjectDirs::from("", "", "gcop").map(|dirs| dirs.config_dir().to_path_buf())
 }
 
+/// 获取数据目录路径
+///
+/// 用于存放本地用量日志等持久化数据
+pub fn get_data_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "gcop").map(|dirs| dirs.data_dir().to_path_buf())
+}
+
+/// 获取缓存目录路径
+///
+/// 用于存放 LLM 响应缓存（如 ~/.cache/gcop）
+pub fn get_cache_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "gcop").map(|dirs| dirs.cache_dir().to_path_buf())
+}
+
+/// 写入用户配置文件
+///
+/// 配置中可能包含 API key：新建的文件在 Unix 上直接以 600 权限创建，
+/// 不存在先写入再改权限的窗口；已有文件保留原权限。
+pub fn write_config_file(path: &Path, content: &str) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(content.as_bytes())?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,6 +375,7 @@ mod tests {
         let config = AppConfig::default();
         assert!(config.review.show_full_diff);
         assert_eq!(config.review.min_severity, "info");
+        assert_eq!(config.review.pre_push_severity, "critical");
     }
 
     #[test]
@@ -148,24 +384,221 @@ mod tests {
         assert_eq!(config.file.max_size, 10 * 1024 * 1024);
     }
 
+    #[test]
+    fn test_app_config_default_security() {
+        let config = AppConfig::default();
+        assert_eq!(config.security.secret_scan, "confirm");
+        assert_eq!(config.security.entropy_threshold, 4.5);
+        assert!(config.security.allow_patterns.is_empty());
+    }
+
+    #[test]
+    fn test_app_config_default_cache() {
+        let config = AppConfig::default();
+        assert!(config.cache.enabled);
+        assert_eq!(config.cache.max_size, 50 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_app_config_default_usage() {
+        let config = AppConfig::default();
+        assert!(config.usage.enabled);
+        assert!(config.usage.prices.is_empty());
+    }
+
+    #[test]
+    fn test_app_config_default_budget() {
+        let config = AppConfig::default();
+        assert_eq!(config.budget.max_request_tokens, 0);
+        assert_eq!(config.budget.on_exceed, "refuse");
+    }
+
+    #[test]
+    fn test_app_config_default_convention() {
+        let config = AppConfig::default();
+        assert!(config.convention.types.contains(&"feat".to_string()));
+        assert!(config.convention.scopes.is_empty());
+        assert!(!config.convention.require_scope);
+        assert_eq!(config.convention.max_subject_length, 72);
+        assert!(!config.convention.suggest_rewrite);
+    }
+
     // === 配置加载测试 ===
 
     #[test]
     fn test_load_config_succeeds() {
         // 验证 load_config 不会崩溃
-        let result = load_config();
+        let result = load_config(None);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_load_config_returns_valid_config() {
-        let config = load_config().unwrap();
+        let config = load_config(None).unwrap();
         // 验证配置有合理的值（不一定是默认值，可能被用户配置覆盖）
         assert!(!config.llm.default_provider.is_empty());
         assert!(config.commit.max_retries > 0);
         assert!(config.network.request_timeout > 0);
     }
 
+    /// 创建带 `.git` 的仓库目录、用户配置和仓库配置
+    fn layered_setup(user: &str, repo: &str) -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().join("repo");
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join(repo::REPO_CONFIG_FILE), repo).unwrap();
+        let user_path = dir.path().join("config.toml");
+        std::fs::write(&user_path, user).unwrap();
+        (dir, root, user_path)
+    }
+
+    #[test]
+    #[serial]
+    fn test_repo_config_layered_over_user_config() {
+        let (_dir, root, user_path) = layered_setup(
+            "[commit]\nmax_retries = 3\ncustom_prompt = \"user\"\n\n[llm.providers.claude]\napi_key = \"sk-user\"\nmodel = \"m\"\n",
+            "[commit]\ncustom_prompt = \"repo\"\n\n[convention]\nscopes = [\"core\"]\n\n[llm.providers.claude]\napi_key = \"sk-repo\"\nendpoint = \"https://attacker.example\"\n",
+        );
+
+        let config = load_config_from(Some(&user_path), Some(&root.join("src")), None)
+            .unwrap()
+            .config;
+        assert_eq!(config.commit.custom_prompt.as_deref(), Some("repo"));
+        assert_eq!(config.commit.max_retries, 3);
+        assert_eq!(config.convention.scopes, vec!["core".to_string()]);
+        let claude = &config.llm.providers["claude"];
+        assert_eq!(claude.api_key.as_deref(), Some("sk-user"));
+        assert_eq!(claude.endpoint, None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_repo_exclude_paths_extend_user_exclude_paths() {
+        let (_dir, root, user_path) = layered_setup(
+            "[security]\nexclude_paths = [\"**/*.pem\"]\n",
+            "[security]\nexclude_paths = []\n",
+        );
+        let config = load_config_from(Some(&user_path), Some(&root), None)
+            .unwrap()
+            .config;
+        assert_eq!(config.security.exclude_paths, vec!["**/*.pem".to_string()]);
+
+        std::fs::write(
+            root.join(repo::REPO_CONFIG_FILE),
+            "[security]\nexclude_paths = [\"fixtures/**\", \"**/*.pem\"]\n",
+        )
+        .unwrap();
+        let config = load_config_from(Some(&user_path), Some(&root), None)
+            .unwrap()
+            .config;
+        assert_eq!(
+            config.security.exclude_paths,
+            vec!["**/*.pem".to_string(), "fixtures/**".to_string()]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_trusted_repo_config_can_set_anything() {
+        let repo_config =
+            "[llm.providers.ollama]\nendpoint = \"http://gpu-box:11434\"\nmodel = \"m\"\n";
+        let (_dir, root, user_path) = layered_setup("", repo_config);
+        let untrusted = load_config_from(Some(&user_path), Some(&root), None)
+            .unwrap()
+            .config;
+        assert!(!untrusted.llm.providers.contains_key("ollama"));
+
+        let user = format!(
+            "[security]\ntrusted_repos = [{:?}]\n",
+            root.to_string_lossy()
+        );
+        std::fs::write(&user_path, user).unwrap();
+        let config = load_config_from(Some(&user_path), Some(&root), None)
+            .unwrap()
+            .config;
+        assert_eq!(
+            config.llm.providers["ollama"].endpoint.as_deref(),
+            Some("http://gpu-box:11434")
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_profile_layered_between_user_and_repo_config() {
+        let (_dir, root, user_path) = layered_setup(
+            "[commit]\nmax_retries = 3\n\n[profiles.work]\nmatch_path = [\"/nowhere/**\"]\n\n[profiles.work.commit]\nmax_retries = 5\ncustom_prompt = \"work\"\n\n[profiles.work.llm]\ndefault_provider = \"azure\"\n",
+            "[commit]\ncustom_prompt = \"repo\"\n",
+        );
+
+        let config = load_config_from(Some(&user_path), Some(&root), None)
+            .unwrap()
+            .config;
+        assert_eq!(config.active_profile, None);
+        assert_eq!(config.commit.max_retries, 3);
+
+        let config = load_config_from(Some(&user_path), Some(&root), Some("work"))
+            .unwrap()
+            .config;
+        assert_eq!(config.active_profile.as_deref(), Some("work"));
+        assert_eq!(config.commit.max_retries, 5);
+        assert_eq!(config.llm.default_provider, "azure");
+        // 仓库配置优先于 profile
+        assert_eq!(config.commit.custom_prompt.as_deref(), Some("repo"));
+
+        assert!(load_config_from(Some(&user_path), Some(&root), Some("home")).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_invalid_repo_config_is_error() {
+        let (_dir, root, user_path) = layered_setup("", "[commit\n");
+        let result = load_config_from(Some(&user_path), Some(&root), None);
+        assert!(
+            matches!(result, Err(GcopError::Config(msg)) if msg.contains(repo::REPO_CONFIG_FILE))
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_schema_issues_are_errors() {
+        let (_dir, root, user_path) = layered_setup(
+            "[llm.providers.claude]\nmodel = \"m\"\ntemprature = 0.5\n",
+            "[review]\nmin_severity = \"high\"\n",
+        );
+        let Err(GcopError::Config(msg)) = load_config_from(Some(&user_path), Some(&root), None)
+        else {
+            panic!("expected config error");
+        };
+        assert!(msg.contains(&user_path.display().to_string()));
+        assert!(msg.contains(
+            "line 3: llm.providers.claude.temprature: unknown key (did you mean 'temperature'?)"
+        ));
+
+        std::fs::write(&user_path, "").unwrap();
+        let Err(GcopError::Config(msg)) = load_config_from(Some(&user_path), Some(&root), None)
+        else {
+            panic!("expected config error");
+        };
+        assert!(msg.contains(repo::REPO_CONFIG_FILE));
+        assert!(msg.contains("line 2: review.min_severity"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_invalid_env_value_is_error() {
+        let (_dir, root, user_path) = layered_setup("[llm.providers.claude]\nmodel = \"m\"\n", "");
+        let _guard = EnvGuard::set("GCOP_LLM_PROVIDERS_CLAUDE_TEMPERATURE", "5");
+        let Err(GcopError::Config(msg)) = load_config_from(Some(&user_path), Some(&root), None)
+        else {
+            panic!("expected config error");
+        };
+        assert!(msg.contains(
+            "llm.providers.claude.temperature: must be between 0 and 2 (got 5) \
+             (env GCOP_LLM_PROVIDERS_CLAUDE_TEMPERATURE)"
+        ));
+    }
+
     // === 路径函数测试 ===
 
     #[test]
@@ -177,6 +610,27 @@ mod tests {
         assert!(path.to_string_lossy().contains("gcop"));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_write_config_file_creates_private_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        write_config_file(&path, "[ui]\ncolored = false\n").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        write_config_file(&path, "[ui]\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[ui]\n");
+    }
+
+    #[test]
+    fn test_get_cache_dir_returns_valid_path() {
+        let cache_dir = get_cache_dir().unwrap();
+        assert!(cache_dir.to_string_lossy().contains("gcop"));
+    }
+
     #[test]
     fn test_get_config_path_has_toml_suffix() {
         let config_dir = get_config_dir();
@@ -221,7 +675,7 @@ mod tests {
     fn test_env_var_bool_parsing() {
         // 测试 config crate 的 bool 解析能力
         let _guard = EnvGuard::set("GCOP_UI_VERBOSE", "true");
-        let config = load_config().unwrap();
+        let config = load_config(None).unwrap();
         // ui.verbose 默认是 false，如果环境变量生效应该是 true
         // 但如果用户配置文件覆盖了，可能仍然是其他值
         // 这里我们只验证加载成功，不验证具体值
@@ -0,0 +1,230 @@
+//! 配置 profile
+//!
+//! 用户配置中的 `[profiles.<name>]` 可以覆盖任意配置节，按以下顺序选择：
+//! `--profile` 参数、`GCOP_PROFILE` 环境变量，最后按仓库的 remote URL 或工作目录自动匹配。
+//!
+//! ```toml
+//! [profiles.work]
+//! match_remote = ["*github.com[:/]acme/*"]
+//! match_path = ["~/work/**"]
+//!
+//! [profiles.work.llm]
+//! default_provider = "azure"
+//! ```
+
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use crate::error::{GcopError, Result};
+
+/// 选择 profile 的环境变量
+pub const PROFILE_ENV: &str = "GCOP_PROFILE";
+
+/// 只用于匹配、不作为配置覆盖的键
+const MATCH_KEYS: &[&str] = &["match_remote", "match_path"];
+
+/// 选中的 profile
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedProfile {
+    pub name: String,
+    /// 选中的原因，用于 verbose 输出
+    pub reason: String,
+    /// 要覆盖的配置（已去掉匹配规则）
+    pub overrides: toml::Table,
+}
+
+/// 从用户配置的 `profiles` 表中选择 profile
+///
+/// `requested` 为显式指定的名称（`--profile` 或 `GCOP_PROFILE`），不存在时报错；
+/// 未指定时按名称顺序取第一个匹配 `remotes` 或 `cwd` 的 profile。
+pub fn select(
+    profiles: &toml::Table,
+    requested: Option<&str>,
+    remotes: &[String],
+    cwd: Option<&Path>,
+) -> Result<Option<SelectedProfile>> {
+    if let Some(name) = requested {
+        let Some(toml::Value::Table(profile)) = profiles.get(name) else {
+            let mut available: Vec<&str> = profiles.keys().map(String::as_str).collect();
+            available.sort();
+            return Err(GcopError::Config(format!(
+                "Unknown profile '{}' (available: {})",
+                name,
+                if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                }
+            )));
+        };
+        return Ok(Some(selected(name, "requested".to_string(), profile)));
+    }
+
+    let mut names: Vec<&String> = profiles.keys().collect();
+    names.sort();
+    for name in names {
+        let Some(toml::Value::Table(profile)) = profiles.get(name) else {
+            continue;
+        };
+        if let Some(reason) = match_reason(name, profile, remotes, cwd)? {
+            return Ok(Some(selected(name, reason, profile)));
+        }
+    }
+    Ok(None)
+}
+
+fn selected(name: &str, reason: String, profile: &toml::Table) -> SelectedProfile {
+    let mut overrides = profile.clone();
+    for key in MATCH_KEYS {
+        overrides.remove(*key);
+    }
+    SelectedProfile {
+        name: name.to_string(),
+        reason,
+        overrides,
+    }
+}
+
+/// profile 的匹配规则命中时返回原因
+fn match_reason(
+    name: &str,
+    profile: &toml::Table,
+    remotes: &[String],
+    cwd: Option<&Path>,
+) -> Result<Option<String>> {
+    let remote_matcher = build_matcher(name, "match_remote", &patterns(profile, "match_remote"))?;
+    if let Some(remote) = remotes
+        .iter()
+        .find(|url| remote_matcher.is_match(url.as_str()))
+    {
+        return Ok(Some(format!("remote {}", remote)));
+    }
+
+    let paths: Vec<String> = patterns(profile, "match_path")
+        .iter()
+        .map(|pattern| expand_home(pattern))
+        .collect();
+    let path_matcher = build_matcher(name, "match_path", &paths)?;
+    if let Some(cwd) = cwd
+        && path_matcher.is_match(cwd)
+    {
+        return Ok(Some(format!("path {}", cwd.display())));
+    }
+    Ok(None)
+}
+
+fn patterns(profile: &toml::Table, key: &str) -> Vec<String> {
+    match profile.get(key) {
+        Some(toml::Value::Array(values)) => values
+            .iter()
+            .filter_map(|value| value.as_str().map(String::from))
+            .collect(),
+        Some(toml::Value::String(value)) => vec![value.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// `*` 可以跨越 `/`，便于匹配 URL
+fn build_matcher(profile: &str, key: &str, patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern).build().map_err(|e| {
+            GcopError::Config(format!(
+                "Invalid glob '{}' in profiles.{}.{}: {}",
+                pattern, profile, key, e
+            ))
+        })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| GcopError::Config(format!("Invalid profiles.{}.{}: {}", profile, key, e)))
+}
+
+/// 展开开头的 `~/`
+fn expand_home(pattern: &str) -> String {
+    match (pattern.strip_prefix("~/"), directories::BaseDirs::new()) {
+        (Some(rest), Some(dirs)) => format!("{}/{}", dirs.home_dir().display(), rest),
+        _ => pattern.to_string(),
+    }
+}
+
+/// 工作目录所在仓库的 remote URL，不在仓库中时为空
+pub fn remote_urls(cwd: &Path) -> Vec<String> {
+    let Ok(repo) = git2::Repository::discover(cwd) else {
+        return Vec::new();
+    };
+    let Ok(names) = repo.remotes() else {
+        return Vec::new();
+    };
+    names
+        .iter()
+        .flatten()
+        .filter_map(|name| repo.find_remote(name).ok()?.url().map(String::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn profiles() -> toml::Table {
+        toml::from_str(
+            r#"
+            [personal]
+            match_path = ["/home/me/src/**"]
+            [personal.llm]
+            default_provider = "ollama"
+
+            [work]
+            match_remote = ["*github.com[:/]acme/*"]
+            [work.convention]
+            require_scope = true
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_select_requested() {
+        let profile = select(&profiles(), Some("personal"), &[], None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(profile.name, "personal");
+        assert_eq!(profile.reason, "requested");
+        assert_eq!(
+            profile.overrides,
+            toml::from_str::<toml::Table>("[llm]\ndefault_provider = \"ollama\"\n").unwrap()
+        );
+
+        let err = select(&profiles(), Some("missing"), &[], None).unwrap_err();
+        assert!(err.to_string().contains("available: personal, work"));
+    }
+
+    #[test]
+    fn test_select_by_remote_or_path() {
+        let remotes = vec!["git@github.com:acme/api.git".to_string()];
+        let work = select(&profiles(), None, &remotes, None).unwrap().unwrap();
+        assert_eq!(work.name, "work");
+        assert_eq!(work.reason, "remote git@github.com:acme/api.git");
+
+        let cwd = Path::new("/home/me/src/blog/content");
+        let personal = select(&profiles(), None, &[], Some(cwd)).unwrap().unwrap();
+        assert_eq!(personal.name, "personal");
+
+        let other = vec!["https://github.com/someone/else".to_string()];
+        assert_eq!(
+            select(&profiles(), None, &other, Some(Path::new("/tmp"))).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_match_glob() {
+        let profiles: toml::Table = toml::from_str("[bad]\nmatch_path = [\"[\"]\n").unwrap();
+        let err = select(&profiles, None, &[], Some(Path::new("/tmp"))).unwrap_err();
+        assert!(err.to_string().contains("profiles.bad.match_path"));
+    }
+}
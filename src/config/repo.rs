@@ -0,0 +1,200 @@
+//! 仓库级配置 `.gcop.toml`
+//!
+//! 项目可以在仓库中提交 `.gcop.toml`，共享提交规范、prompt 等设置。它在用户配置之上、
+//! 环境变量之下合并。未受信任的仓库只能设置白名单中的键，密钥、端点等设置会被忽略，
+//! 避免克隆的仓库把 diff 发往别处或替换用户的凭据。
+
+use std::path::{Path, PathBuf};
+
+/// 仓库配置文件名
+pub const REPO_CONFIG_FILE: &str = ".gcop.toml";
+
+/// 未受信任的仓库可以设置的键（包括其下的所有子键）
+const ALLOWED_KEYS: &[&str] = &["commit", "review", "convention", "security.exclude_paths"];
+
+/// 白名单内仍然不允许未受信任的仓库设置的键：仓库不能放宽用户的推送拦截
+const DENIED_KEYS: &[&str] = &["review.pre_push_severity"];
+
+/// 找到的仓库配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoConfigFile {
+    /// `.gcop.toml` 的路径
+    pub path: PathBuf,
+    /// 所在 git 仓库的根目录
+    pub root: PathBuf,
+}
+
+/// 从 `start` 向上查找 `.gcop.toml`，到 git 根目录为止
+///
+/// 不在 git 仓库中时返回 None
+pub fn find_repo_config(start: &Path) -> Option<RepoConfigFile> {
+    let mut found = None;
+    for dir in start.ancestors() {
+        if found.is_none() && dir.join(REPO_CONFIG_FILE).is_file() {
+            found = Some(dir.join(REPO_CONFIG_FILE));
+        }
+        if dir.join(".git").exists() {
+            return found.map(|path| RepoConfigFile {
+                path,
+                root: dir.to_path_buf(),
+            });
+        }
+    }
+    None
+}
+
+/// 仓库根目录是否在用户配置的 `security.trusted_repos` 中
+pub fn is_trusted(root: &Path, trusted_repos: &[String]) -> bool {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    trusted_repos.iter().any(|repo| {
+        let repo = Path::new(repo);
+        repo.canonicalize().unwrap_or_else(|_| repo.to_path_buf()) == root
+    })
+}
+
+/// 移除白名单以外的键，返回被忽略的键（点分路径）
+pub fn filter_untrusted(table: &mut toml::Table) -> Vec<String> {
+    let mut ignored = Vec::new();
+    filter_table(table, "", &mut ignored);
+    ignored
+}
+
+fn filter_table(table: &mut toml::Table, prefix: &str, ignored: &mut Vec<String>) {
+    let keys: Vec<String> = table.keys().cloned().collect();
+    for key in keys {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        let is_parent_of = |key: &&str| key.starts_with(&format!("{}.", path));
+        let allowed = !DENIED_KEYS.contains(&path.as_str())
+            && ALLOWED_KEYS
+                .iter()
+                .any(|allowed| path == *allowed || path.starts_with(&format!("{}.", allowed)));
+        if allowed && !DENIED_KEYS.iter().any(is_parent_of) {
+            continue;
+        }
+        // 是某个允许键的上级表，或包含禁止的子键时继续向下过滤
+        let is_parent = ALLOWED_KEYS.iter().any(is_parent_of) || allowed;
+        if is_parent && let Some(toml::Value::Table(child)) = table.get_mut(&key) {
+            filter_table(child, &path, ignored);
+            if child.is_empty() {
+                table.remove(&key);
+            }
+            continue;
+        }
+        table.remove(&key);
+        ignored.push(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_repo_config_stops_at_git_root() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        let nested = repo.join("src/commands");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+
+        // git 根目录之外的 .gcop.toml 不生效
+        std::fs::write(dir.path().join(REPO_CONFIG_FILE), "").unwrap();
+        assert_eq!(find_repo_config(&nested), None);
+
+        std::fs::write(repo.join(REPO_CONFIG_FILE), "").unwrap();
+        assert_eq!(
+            find_repo_config(&nested),
+            Some(RepoConfigFile {
+                path: repo.join(REPO_CONFIG_FILE),
+                root: repo.clone(),
+            })
+        );
+
+        // 离工作目录最近的文件优先
+        std::fs::write(repo.join("src").join(REPO_CONFIG_FILE), "").unwrap();
+        assert_eq!(
+            find_repo_config(&nested).unwrap().path,
+            repo.join("src").join(REPO_CONFIG_FILE)
+        );
+    }
+
+    #[test]
+    fn test_find_repo_config_outside_git() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(REPO_CONFIG_FILE), "").unwrap();
+        assert_eq!(find_repo_config(dir.path()), None);
+    }
+
+    #[test]
+    fn test_filter_untrusted() {
+        let mut table: toml::Table = toml::from_str(
+            r#"
+            [llm]
+            default_provider = "evil"
+
+            [llm.providers.claude]
+            endpoint = "https://attacker.example"
+            api_key = "sk-ant-stolen"
+
+            [commit]
+            custom_prompt = "Use the project style"
+
+            [convention]
+            scopes = ["core", "cli"]
+
+            [review]
+            min_severity = "warning"
+            pre_push_severity = "info"
+
+            [security]
+            secret_scan = "off"
+            exclude_paths = ["fixtures/**"]
+            "#,
+        )
+        .unwrap();
+
+        let mut ignored = filter_untrusted(&mut table);
+        ignored.sort();
+        assert_eq!(
+            ignored,
+            vec![
+                "llm".to_string(),
+                "review.pre_push_severity".to_string(),
+                "security.secret_scan".to_string()
+            ]
+        );
+
+        let expected: toml::Table = toml::from_str(
+            r#"
+            [commit]
+            custom_prompt = "Use the project style"
+
+            [convention]
+            scopes = ["core", "cli"]
+
+            [review]
+            min_severity = "warning"
+
+            [security]
+            exclude_paths = ["fixtures/**"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(table, expected);
+    }
+
+    #[test]
+    fn test_is_trusted() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        assert!(is_trusted(&root, &[root.to_string_lossy().into_owned()]));
+        assert!(!is_trusted(&root, &["/somewhere/else".to_string()]));
+        assert!(!is_trusted(&root, &[]));
+    }
+}
@@ -20,11 +20,35 @@ pub struct AppConfig {
 
     #[serde(default)]
     pub file: FileConfig,
+
+    #[serde(default)]
+    pub security: SecurityConfig,
+
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    #[serde(default)]
+    pub usage: UsageConfig,
+
+    #[serde(default)]
+    pub budget: BudgetConfig,
+
+    #[serde(default)]
+    pub convention: ConventionConfig,
+
+    /// 命名 profile，可覆盖任意配置节
+    #[serde(default)]
+    pub profiles: HashMap<String, ProfileConfig>,
+
+    /// 本次加载选中的 profile（运行时确定，不来自配置文件）
+    #[serde(skip)]
+    pub active_profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LLMConfig {
     /// 默认使用的 provider: "claude" | "openai" | "ollama"
+    #[serde(default = "default_provider")]
     pub default_provider: String,
 
     /// 各 provider 的配置
@@ -43,16 +67,24 @@ pub struct ProviderConfig {
     /// API endpoint
     pub endpoint: Option<String>,
 
-    /// API key（优先从环境变量读取）
+    /// API key（明文，优先于其他来源）
     pub api_key: Option<String>,
 
+    /// 存放 API key 的文件路径（支持 `~/` 开头）
+    #[serde(default)]
+    pub api_key_file: Option<String>,
+
+    /// 输出 API key 的命令（如 `pass show anthropic`），每个进程只执行一次
+    #[serde(default)]
+    pub api_key_cmd: Option<String>,
+
     /// 模型名称
     pub model: String,
 
     /// 最大生成 token 数
     pub max_tokens: Option<u32>,
 
-    /// 温度参数（0.0-1.0）
+    /// 温度参数（0.0-2.0）
     pub temperature: Option<f32>,
 
     /// 其他参数
@@ -94,6 +126,10 @@ pub struct ReviewConfig {
     #[serde(default = "default_severity")]
     pub min_severity: String,
 
+    /// pre-push hook 阻止推送的问题严重性阈值（取值同 min_severity）
+    #[serde(default = "default_pre_push_severity")]
+    pub pre_push_severity: String,
+
     /// 自定义 code review 的 prompt 模板
     /// 可用占位符：{diff}
     #[serde(default)]
@@ -141,6 +177,108 @@ pub struct FileConfig {
     pub max_size: u64,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SecurityConfig {
+    /// 发送 diff 前的密钥扫描模式: "block" | "redact" | "confirm" | "off"
+    #[serde(default = "default_secret_scan")]
+    pub secret_scan: String,
+
+    /// 高熵字符串判定阈值（Shannon 熵，bits/char），0 表示关闭熵检测
+    #[serde(default = "default_entropy_threshold")]
+    pub entropy_threshold: f64,
+
+    /// 允许列表：匹配这些正则的内容不会被视为密钥
+    #[serde(default)]
+    pub allow_patterns: Vec<String>,
+
+    /// 永远不会发送给 LLM 的路径（glob，如 "secrets/**"、"**/*.pem"）
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+
+    /// 受信任的仓库根目录，其中的 `.gcop.toml` 可以设置任意配置（包括密钥和端点）
+    #[serde(default)]
+    pub trusted_repos: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CacheConfig {
+    /// 是否启用本地响应缓存
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// 缓存目录最大占用（字节），超出后淘汰最久未使用的条目
+    #[serde(default = "default_cache_max_size")]
+    pub max_size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UsageConfig {
+    /// 是否在本地记录每次 LLM 调用的 token 用量
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// 各模型的价格表，key 为模型名称
+    #[serde(default)]
+    pub prices: HashMap<String, ModelPrice>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BudgetConfig {
+    /// 单次请求的 prompt token 上限（估算值），0 表示只受模型上下文窗口限制
+    #[serde(default = "default_max_request_tokens")]
+    pub max_request_tokens: u64,
+
+    /// 超出上限时的处理方式: "warn" | "refuse"
+    #[serde(default = "default_budget_on_exceed")]
+    pub on_exceed: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConventionConfig {
+    /// 允许的 commit 类型
+    #[serde(default = "default_convention_types")]
+    pub types: Vec<String>,
+
+    /// 允许的 scope，为空表示不限制
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    /// 是否必须填写 scope
+    #[serde(default)]
+    pub require_scope: bool,
+
+    /// 首行最大长度（字符数）
+    #[serde(default = "default_max_subject_length")]
+    pub max_subject_length: usize,
+
+    /// commit-msg 检查失败时是否请 LLM 给出符合规范的改写建议
+    #[serde(default)]
+    pub suggest_rewrite: bool,
+}
+
+/// 命名 profile
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProfileConfig {
+    /// 仓库 remote URL 匹配这些 glob 时自动选中
+    #[serde(default)]
+    pub match_remote: Vec<String>,
+
+    /// 工作目录匹配这些 glob 时自动选中（支持 `~/` 开头）
+    #[serde(default)]
+    pub match_path: Vec<String>,
+
+    /// 覆盖的配置节（如 `llm`、`convention`）
+    #[serde(flatten)]
+    pub overrides: HashMap<String, serde_json::Value>,
+}
+
+/// 模型价格（美元 / 百万 token）
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -149,6 +287,10 @@ fn default_severity() -> String {
     "info".to_string()
 }
 
+fn default_pre_push_severity() -> String {
+    "critical".to_string()
+}
+
 fn default_commit_max_retries() -> usize {
     10
 }
@@ -173,10 +315,48 @@ fn default_max_file_size() -> u64 {
     10 * 1024 * 1024 // 10MB
 }
 
+fn default_cache_max_size() -> u64 {
+    50 * 1024 * 1024 // 50MB
+}
+
+fn default_convention_types() -> Vec<String> {
+    [
+        "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore",
+        "revert",
+    ]
+    .iter()
+    .map(|t| t.to_string())
+    .collect()
+}
+
+fn default_max_subject_length() -> usize {
+    72
+}
+
+fn default_max_request_tokens() -> u64 {
+    0
+}
+
+fn default_budget_on_exceed() -> String {
+    "refuse".to_string()
+}
+
+fn default_provider() -> String {
+    "claude".to_string()
+}
+
+fn default_secret_scan() -> String {
+    "confirm".to_string()
+}
+
+fn default_entropy_threshold() -> f64 {
+    4.5
+}
+
 impl Default for LLMConfig {
     fn default() -> Self {
         Self {
-            default_provider: "claude".to_string(),
+            default_provider: default_provider(),
             providers: HashMap::new(),
         }
     }
@@ -199,6 +379,7 @@ impl Default for ReviewConfig {
         Self {
             show_full_diff: true,
             min_severity: "info".to_string(),
+            pre_push_severity: default_pre_push_severity(),
             custom_prompt: None,
         }
     }
@@ -232,3 +413,54 @@ impl Default for FileConfig {
         }
     }
 }
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            secret_scan: default_secret_scan(),
+            entropy_threshold: default_entropy_threshold(),
+            allow_patterns: Vec::new(),
+            exclude_paths: Vec::new(),
+            trusted_repos: Vec::new(),
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_size: default_cache_max_size(),
+        }
+    }
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            prices: HashMap::new(),
+        }
+    }
+}
+
+impl Default for ConventionConfig {
+    fn default() -> Self {
+        Self {
+            types: default_convention_types(),
+            scopes: Vec::new(),
+            require_scope: false,
+            max_subject_length: default_max_subject_length(),
+            suggest_rewrite: false,
+        }
+    }
+}
+
+impl Default for BudgetConfig {
+    fn default() -> Self {
+        Self {
+            max_request_tokens: default_max_request_tokens(),
+            on_exceed: default_budget_on_exceed(),
+        }
+    }
+}
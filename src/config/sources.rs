@@ -0,0 +1,95 @@
+//! 配置值来源追踪
+//!
+//! 加载配置时记录每一层（用户配置、profile、仓库配置、环境变量）设置了哪些键，
+//! `config show` 据此标注每个值来自哪里。
+
+use std::collections::BTreeSet;
+
+use super::AppConfig;
+
+/// 配置中的一层
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLayer {
+    /// 来源说明，如 `config.toml`、`profile work`、`env GCOP_UI_COLORED`
+    pub label: String,
+    /// 该层设置的键（点分路径，数组视为一个值）
+    pub keys: BTreeSet<String>,
+}
+
+impl ConfigLayer {
+    pub fn from_table(label: impl Into<String>, table: &toml::Table) -> Self {
+        let mut keys = BTreeSet::new();
+        collect_keys(table, "", &mut keys);
+        Self {
+            label: label.into(),
+            keys,
+        }
+    }
+}
+
+fn collect_keys(table: &toml::Table, prefix: &str, keys: &mut BTreeSet<String>) {
+    for (key, value) in table {
+        let path = join_key(prefix, key);
+        match value {
+            toml::Value::Table(child) => collect_keys(child, &path, keys),
+            _ => {
+                keys.insert(path);
+            }
+        }
+    }
+}
+
+/// 拼接点分路径
+pub fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// 加载后的配置及其各层来源（按优先级从低到高）
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub config: AppConfig,
+    pub layers: Vec<ConfigLayer>,
+}
+
+impl LoadedConfig {
+    /// 键的生效值来自哪一层：最后一个设置了它的层，都没有设置时为 `default`
+    pub fn source_of(&self, key: &str) -> &str {
+        self.layers
+            .iter()
+            .rev()
+            .find(|layer| layer.keys.contains(key))
+            .map_or("default", |layer| layer.label.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_source_of_last_layer_wins() {
+        let user: toml::Table =
+            toml::from_str("[commit]\nmax_retries = 3\n[convention]\nscopes = [\"a\"]\n").unwrap();
+        let repo: toml::Table = toml::from_str("[commit]\nmax_retries = 5\n").unwrap();
+        let loaded = LoadedConfig {
+            config: AppConfig::default(),
+            layers: vec![
+                ConfigLayer::from_table("config.toml", &user),
+                ConfigLayer::from_table(".gcop.toml", &repo),
+            ],
+        };
+
+        assert_eq!(
+            loaded.layers[0].keys.iter().collect::<Vec<_>>(),
+            vec!["commit.max_retries", "convention.scopes"]
+        );
+        assert_eq!(loaded.source_of("commit.max_retries"), ".gcop.toml");
+        assert_eq!(loaded.source_of("convention.scopes"), "config.toml");
+        assert_eq!(loaded.source_of("ui.colored"), "default");
+    }
+}
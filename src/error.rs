@@ -34,6 +34,21 @@ pub enum GcopError {
     #[error("No staged changes found")]
     NoStagedChanges,
 
+    #[error("Potential secrets detected in {0} location(s) of the diff")]
+    SecretsDetected(usize),
+
+    #[error("Prompt too large: ~{estimated} tokens exceeds the limit of {limit}")]
+    PromptTooLarge { estimated: usize, limit: usize },
+
+    #[error("Commit message violates {0} convention rule(s)")]
+    CommitMessageInvalid(usize),
+
+    #[error("Push blocked: {0} review issue(s) at or above the configured severity")]
+    ReviewGateFailed(usize),
+
+    #[error("Review found {count} issue(s) at or above '{threshold}' severity")]
+    ReviewThresholdExceeded { count: usize, threshold: String },
+
     #[error("Operation cancelled by user")]
     UserCancelled,
 
@@ -66,6 +81,21 @@ impl GcopError {
             GcopError::Config(msg) if msg.contains("not found in config") => Some(
                 "Check your ~/.config/gcop/config.toml or use the default providers: claude, openai, ollama",
             ),
+            GcopError::Config(msg) if msg.starts_with("Invalid configuration") => Some(
+                "Fix the listed keys with 'gcop-rs config edit', or use 'gcop-rs config set' / 'gcop-rs config unset'",
+            ),
+            GcopError::SecretsDetected(_) => Some(
+                "Remove the secrets from your changes, whitelist false positives via security.allow_patterns, or set security.secret_scan = \"redact\"",
+            ),
+            GcopError::CommitMessageInvalid(_) => Some(
+                "Fix the message to match [convention] in your config, or bypass the check once with 'git commit --no-verify'",
+            ),
+            GcopError::ReviewGateFailed(_) => Some(
+                "Fix the reported issues, or push anyway with 'GCOP_SKIP_REVIEW=1 git push' (or 'git push --no-verify')",
+            ),
+            GcopError::PromptTooLarge { .. } => Some(
+                "Stage fewer files, exclude generated files via security.exclude_paths, or raise budget.max_request_tokens",
+            ),
             GcopError::Network(_) => {
                 Some("Check your network connection, proxy settings, or API endpoint configuration")
             }
@@ -140,6 +170,70 @@ mod tests {
         assert!(suggestion.contains("claude, openai, ollama"));
     }
 
+    #[test]
+    fn test_suggestion_config_invalid() {
+        let err = GcopError::Config(
+            "Invalid configuration in config.toml:\n  line 3: ui.shiny: unknown key".to_string(),
+        );
+        assert!(err.suggestion().unwrap().contains("gcop-rs config edit"));
+    }
+
+    // === SecretsDetected 分支 ===
+
+    #[test]
+    fn test_suggestion_secrets_detected() {
+        let err = GcopError::SecretsDetected(2);
+        let suggestion = err.suggestion().unwrap();
+        assert!(suggestion.contains("security.allow_patterns"));
+        assert!(suggestion.contains("redact"));
+    }
+
+    // === CommitMessageInvalid 分支 ===
+
+    #[test]
+    fn test_suggestion_commit_message_invalid() {
+        let err = GcopError::CommitMessageInvalid(2);
+        let suggestion = err.suggestion().unwrap();
+        assert!(suggestion.contains("--no-verify"));
+    }
+
+    // === ReviewGateFailed 分支 ===
+
+    #[test]
+    fn test_suggestion_review_gate_failed() {
+        let err = GcopError::ReviewGateFailed(1);
+        let suggestion = err.suggestion().unwrap();
+        assert!(suggestion.contains("GCOP_SKIP_REVIEW"));
+    }
+
+    // === ReviewThresholdExceeded 分支 ===
+
+    #[test]
+    fn test_review_threshold_exceeded_has_no_suggestion() {
+        // CI 模式下 stdout 是报告本身，不应再追加 Tip
+        let err = GcopError::ReviewThresholdExceeded {
+            count: 2,
+            threshold: "warning".to_string(),
+        };
+        assert!(err.suggestion().is_none());
+        assert_eq!(
+            err.to_string(),
+            "Review found 2 issue(s) at or above 'warning' severity"
+        );
+    }
+
+    // === PromptTooLarge 分支 ===
+
+    #[test]
+    fn test_suggestion_prompt_too_large() {
+        let err = GcopError::PromptTooLarge {
+            estimated: 250_000,
+            limit: 100_000,
+        };
+        let suggestion = err.suggestion().unwrap();
+        assert!(suggestion.contains("budget.max_request_tokens"));
+    }
+
     // === Network 错误 ===
 
     #[test]
@@ -0,0 +1,248 @@
+//! Commit message 规范检查
+//!
+//! 按 `[convention]` 配置检查 conventional commits 格式：
+//! `type(scope)!: subject`，首行长度、类型与 scope 白名单等。
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::config::ConventionConfig;
+
+/// 首行格式：type(scope)!: subject
+static HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^()]*)\))?(?P<breaking>!)?: (?P<subject>.*)$")
+        .expect("valid header regex")
+});
+
+/// git 自动生成、不参与检查的消息前缀
+const EXEMPT_PREFIXES: &[&str] = &["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+/// 一条违规
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintViolation {
+    /// 规则名
+    pub rule: &'static str,
+    /// 可读的说明
+    pub message: String,
+}
+
+impl fmt::Display for LintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.rule)
+    }
+}
+
+impl LintViolation {
+    fn new(rule: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+        }
+    }
+}
+
+/// 去掉注释行和 `git commit -v` 剪刀线之后的内容
+pub fn strip_comments(message: &str) -> String {
+    message
+        .lines()
+        .take_while(|line| !(line.starts_with("# -") && line.contains(">8")))
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// 检查 commit message（应先经过 [`strip_comments`]）
+pub fn lint_message(message: &str, convention: &ConventionConfig) -> Vec<LintViolation> {
+    let mut violations = Vec::new();
+
+    let mut lines = message.lines();
+    let header = lines.next().unwrap_or("").trim_end();
+    if header.trim().is_empty() {
+        violations.push(LintViolation::new("empty", "Commit message is empty"));
+        return violations;
+    }
+
+    if EXEMPT_PREFIXES.iter().any(|p| header.starts_with(p)) {
+        return violations;
+    }
+
+    let header_len = header.chars().count();
+    if header_len > convention.max_subject_length {
+        violations.push(LintViolation::new(
+            "header-max-length",
+            format!(
+                "First line is {} characters, max is {}",
+                header_len, convention.max_subject_length
+            ),
+        ));
+    }
+
+    if let Some(second) = lines.next()
+        && !second.trim().is_empty()
+    {
+        violations.push(LintViolation::new(
+            "body-leading-blank",
+            "Separate the first line from the body with a blank line",
+        ));
+    }
+
+    let Some(caps) = HEADER_RE.captures(header) else {
+        violations.push(LintViolation::new(
+            "header-format",
+            "First line must look like 'type(scope): subject'",
+        ));
+        return violations;
+    };
+
+    let commit_type = &caps["type"];
+    if !convention.types.is_empty() && !convention.types.iter().any(|t| t == commit_type) {
+        violations.push(LintViolation::new(
+            "type-enum",
+            format!(
+                "Type '{}' is not allowed, use one of: {}",
+                commit_type,
+                convention.types.join(", ")
+            ),
+        ));
+    }
+
+    match caps.name("scope").map(|m| m.as_str().trim()) {
+        None | Some("") if convention.require_scope => {
+            violations.push(LintViolation::new("scope-empty", "A scope is required"));
+        }
+        Some(scope)
+            if !scope.is_empty()
+                && !convention.scopes.is_empty()
+                && !convention.scopes.iter().any(|s| s == scope) =>
+        {
+            violations.push(LintViolation::new(
+                "scope-enum",
+                format!(
+                    "Scope '{}' is not allowed, use one of: {}",
+                    scope,
+                    convention.scopes.join(", ")
+                ),
+            ));
+        }
+        _ => {}
+    }
+
+    let subject = caps["subject"].trim();
+    if subject.is_empty() {
+        violations.push(LintViolation::new(
+            "subject-empty",
+            "Subject must not be empty",
+        ));
+    } else if subject.ends_with('.') {
+        violations.push(LintViolation::new(
+            "subject-full-stop",
+            "Subject must not end with a period",
+        ));
+    }
+
+    violations
+}
+
+/// 将规范描述为若干条规则（用于 LLM 改写 prompt）
+pub fn describe_rules(convention: &ConventionConfig) -> Vec<String> {
+    let mut rules = vec!["First line format: type(scope): subject".to_string()];
+    if !convention.types.is_empty() {
+        rules.push(format!("Allowed types: {}", convention.types.join(", ")));
+    }
+    if convention.require_scope {
+        rules.push("A scope is required".to_string());
+    }
+    if !convention.scopes.is_empty() {
+        rules.push(format!("Allowed scopes: {}", convention.scopes.join(", ")));
+    }
+    rules.push(format!(
+        "First line at most {} characters",
+        convention.max_subject_length
+    ));
+    rules.push("Subject does not end with a period".to_string());
+    rules.push("Blank line between the first line and the body".to_string());
+    rules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rules(violations: &[LintViolation]) -> Vec<&'static str> {
+        violations.iter().map(|v| v.rule).collect()
+    }
+
+    #[test]
+    fn test_valid_messages_pass() {
+        let convention = ConventionConfig::default();
+        assert!(lint_message("feat: add hook", &convention).is_empty());
+        assert!(lint_message("fix(parser)!: handle empty input", &convention).is_empty());
+        assert!(
+            lint_message("docs: update readme\n\nExplain the new flag.", &convention).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_git_generated_messages_are_exempt() {
+        let convention = ConventionConfig::default();
+        assert!(lint_message("Merge branch 'main' into feature", &convention).is_empty());
+        assert!(lint_message("fixup! feat: add hook", &convention).is_empty());
+    }
+
+    #[test]
+    fn test_empty_message() {
+        let convention = ConventionConfig::default();
+        assert_eq!(rules(&lint_message("", &convention)), vec!["empty"]);
+    }
+
+    #[test]
+    fn test_bad_format_and_length() {
+        let convention = ConventionConfig {
+            max_subject_length: 20,
+            ..Default::default()
+        };
+        let violations = lint_message("Updated a bunch of stuff in the parser", &convention);
+        assert_eq!(
+            rules(&violations),
+            vec!["header-max-length", "header-format"]
+        );
+    }
+
+    #[test]
+    fn test_type_and_scope_rules() {
+        let convention = ConventionConfig {
+            scopes: vec!["api".to_string(), "cli".to_string()],
+            require_scope: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            rules(&lint_message("feature: add thing", &convention)),
+            vec!["type-enum", "scope-empty"]
+        );
+        assert_eq!(
+            rules(&lint_message("fix(db): repair.", &convention)),
+            vec!["scope-enum", "subject-full-stop"]
+        );
+        assert!(lint_message("fix(cli): repair", &convention).is_empty());
+    }
+
+    #[test]
+    fn test_body_needs_blank_line() {
+        let convention = ConventionConfig::default();
+        assert_eq!(
+            rules(&lint_message("feat: add\nbody directly", &convention)),
+            vec!["body-leading-blank"]
+        );
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        let raw = "feat: add\n\n# Please enter the commit message\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+        assert_eq!(strip_comments(raw), "feat: add");
+    }
+}
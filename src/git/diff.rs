@@ -1,6 +1,18 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use crate::error::Result;
 use crate::git::DiffStats;
 
+/// diff 中的新增行（带文件路径和新文件行号）
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddedLine {
+    /// 在 diff 文本中的行索引（从 0 开始）
+    pub index: usize,
+    pub file: String,
+    pub line: usize,
+    pub content: String,
+}
+
 /// 从 diff 文本中提取统计信息
 pub fn parse_diff_stats(diff: &str) -> Result<DiffStats> {
     let mut files_changed = Vec::new();
@@ -30,6 +42,534 @@ pub fn parse_diff_stats(diff: &str) -> Result<DiffStats> {
     })
 }
 
+/// 单个文件的 diff 片段
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileDiff {
+    /// 文件路径（优先取新文件路径，删除文件取旧路径）
+    pub path: String,
+    /// 该文件完整的 diff 文本（包含 `diff --git` 头）
+    pub text: String,
+}
+
+/// 按文件拆分 diff
+///
+/// 没有 `diff --git` 头的文本视为单个文件。
+pub fn split_file_diffs(diff: &str) -> Vec<FileDiff> {
+    let mut sections: Vec<String> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("diff --git") || sections.is_empty() {
+            sections.push(String::new());
+        }
+        if let Some(current) = sections.last_mut() {
+            current.push_str(line);
+        }
+    }
+
+    sections
+        .into_iter()
+        .filter(|text| !text.trim().is_empty())
+        .map(|text| FileDiff {
+            path: file_diff_path(&text).unwrap_or_default(),
+            text,
+        })
+        .collect()
+}
+
+/// 提取文件 diff 片段对应的路径
+fn file_diff_path(text: &str) -> Option<String> {
+    let mut old_path = None;
+    for line in text.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            if path != "/dev/null" {
+                return Some(path.strip_prefix("b/").unwrap_or(path).to_string());
+            }
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            if path != "/dev/null" {
+                old_path = Some(path.strip_prefix("a/").unwrap_or(path).to_string());
+            }
+        } else if line.starts_with("@@") {
+            break;
+        }
+    }
+
+    // 二进制文件、纯 rename 等没有 ---/+++ 行，回退到 diff --git 头
+    old_path.or_else(|| {
+        text.lines()
+            .next()
+            .and_then(|header| header.strip_prefix("diff --git "))
+            .and_then(|rest| rest.split_whitespace().nth(1))
+            .map(|b| b.strip_prefix("b/").unwrap_or(b).to_string())
+    })
+}
+
+/// 将匹配规则的文件从 diff 中剔除，替换为一行占位说明
+///
+/// 保留 `diff --git` 头，使被剔除的文件仍计入 [`DiffStats::files_changed`]。
+///
+/// # Returns
+/// (过滤后的 diff, 被剔除的文件路径)
+pub fn exclude_paths(diff: &str, is_excluded: impl Fn(&str) -> bool) -> (String, Vec<String>) {
+    let mut output = String::with_capacity(diff.len());
+    let mut excluded = Vec::new();
+
+    for file in split_file_diffs(diff) {
+        if file.path.is_empty() || !is_excluded(&file.path) {
+            output.push_str(&file.text);
+            continue;
+        }
+
+        output.push_str(&format!(
+            "diff --git a/{path} b/{path}\n[gcop-rs] Changes to {path} omitted by exclusion rules\n",
+            path = file.path
+        ));
+        excluded.push(file.path);
+    }
+
+    (output, excluded)
+}
+
+/// 解析 hunk 头中新文件的起始行号：`@@ -a,b +c,d @@` -> c
+pub fn parse_hunk_new_start(header: &str) -> Option<usize> {
+    let rest = header.strip_prefix("@@ ")?;
+    let new_part = rest.split_whitespace().find(|p| p.starts_with('+'))?;
+    new_part[1..].split(',').next()?.parse().ok()
+}
+
+/// diff 中一行的类别（带新文件行号）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    /// 文件头、hunk 头等元信息
+    Meta,
+    Added(usize),
+    Context(usize),
+    Removed,
+}
+
+impl LineKind {
+    /// 在新文件中的行号，删除行和元信息行为 None
+    fn new_line(self) -> Option<usize> {
+        match self {
+            LineKind::Added(n) | LineKind::Context(n) => Some(n),
+            LineKind::Meta | LineKind::Removed => None,
+        }
+    }
+}
+
+/// hunk 头中的新旧行数：`@@ -a,b +c,d @@` -> (b, d)，省略时为 1
+fn parse_hunk_lengths(header: &str) -> Option<(usize, usize)> {
+    let rest = header.strip_prefix("@@ ")?;
+    let mut parts = rest.split_whitespace();
+    let mut length = |sign: char| -> Option<usize> {
+        let range = parts.next()?.strip_prefix(sign)?;
+        match range.split_once(',') {
+            Some((_, length)) => length.parse().ok(),
+            None => Some(1),
+        }
+    };
+    Some((length('-')?, length('+')?))
+}
+
+/// 逐行遍历 diff，计算每行所属文件和在新文件中的行号
+///
+/// hunk 内按 hunk 头中的行数计数，因此内容以 `++ ` 开头的新增行（`+++ ...`）
+/// 不会被当作文件头；`@@` 和 `diff --git` 不可能是 hunk 内容，遇到时总是结束 hunk。
+/// hunk 以外的行都是元信息。
+fn for_each_line<'a>(diff: &'a str, mut f: impl FnMut(usize, &'a str, Option<&str>, LineKind)) {
+    let mut file: Option<String> = None;
+    let mut next_line = 0;
+    // hunk 中剩余的旧文件 / 新文件行数
+    let mut old_remaining: usize = 0;
+    let mut new_remaining: usize = 0;
+
+    for (index, line) in diff.lines().enumerate() {
+        let in_hunk = old_remaining > 0 || new_remaining > 0;
+        let kind = if in_hunk && !line.starts_with("@@") && !line.starts_with("diff --git") {
+            match line.chars().next() {
+                Some('+') => {
+                    new_remaining = new_remaining.saturating_sub(1);
+                    next_line += 1;
+                    LineKind::Added(next_line - 1)
+                }
+                Some('-') => {
+                    old_remaining = old_remaining.saturating_sub(1);
+                    LineKind::Removed
+                }
+                Some(' ') | None => {
+                    old_remaining = old_remaining.saturating_sub(1);
+                    new_remaining = new_remaining.saturating_sub(1);
+                    next_line += 1;
+                    LineKind::Context(next_line - 1)
+                }
+                // `\ No newline at end of file`
+                _ => LineKind::Meta,
+            }
+        } else if line.starts_with("diff --git") {
+            file = None;
+            old_remaining = 0;
+            new_remaining = 0;
+            LineKind::Meta
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            file = match path {
+                "/dev/null" => None,
+                p => Some(p.strip_prefix("b/").unwrap_or(p).to_string()),
+            };
+            LineKind::Meta
+        } else if line.starts_with("@@") {
+            if let (Some(start), Some((old, new))) =
+                (parse_hunk_new_start(line), parse_hunk_lengths(line))
+            {
+                next_line = start;
+                old_remaining = old;
+                new_remaining = new;
+            }
+            LineKind::Meta
+        } else {
+            LineKind::Meta
+        };
+        f(index, line, file.as_deref(), kind);
+    }
+}
+
+/// 提取 diff 中所有新增行及其在新文件中的行号
+pub fn added_lines(diff: &str) -> Vec<AddedLine> {
+    let mut result = Vec::new();
+    for_each_line(diff, |index, line, file, kind| {
+        // 去掉 `+` 前缀
+        let LineKind::Added(number) = kind else {
+            return;
+        };
+        let content = &line[1..];
+        if let Some(file) = file {
+            result.push(AddedLine {
+                index,
+                file: file.to_string(),
+                line: number,
+                content: content.to_string(),
+            });
+        }
+    });
+    result
+}
+
+/// 行号标注的宽度
+const LINE_NUMBER_WIDTH: usize = 5;
+
+/// 问题行号允许吸附到最近变更行的最大距离
+const MAX_SNAP_DISTANCE: usize = 10;
+
+/// 新增整个文件的 hunk（如 review file 的文件内容）中，[`hunk_at`] 截取目标行前后的行数
+const SNIPPET_CONTEXT: usize = 8;
+
+/// 给一行加上行号前缀（删除行只留空白，元信息行不变）
+fn annotate_line(output: &mut String, line: &str, kind: LineKind) {
+    match kind {
+        LineKind::Meta => {}
+        _ => match kind.new_line() {
+            Some(number) => {
+                output.push_str(&format!("{:>width$} ", number, width = LINE_NUMBER_WIDTH))
+            }
+            None => output.push_str(&" ".repeat(LINE_NUMBER_WIDTH + 1)),
+        },
+    }
+    output.push_str(line);
+    output.push('\n');
+}
+
+/// 在 diff 每行前标注其在新文件中的行号
+///
+/// 删除行只留空白，文件头、hunk 头等元信息行保持原样。
+/// 用于审查 prompt，让模型引用真实行号而不是 hunk 内的相对位置。
+pub fn annotate_line_numbers(diff: &str) -> String {
+    let mut output = String::with_capacity(diff.len() + diff.len() / 4);
+    for_each_line(diff, |_, line, _, kind| {
+        annotate_line(&mut output, line, kind)
+    });
+    output
+}
+
+/// 取出包含某一行的 hunk（带文件头，并标注行号）
+///
+/// `line` 为 None 时返回整个文件的 diff；目标行在新增整个文件的 hunk 中时只截取其前后各
+/// [`SNIPPET_CONTEXT`] 行，并相应改写 hunk 头。文件或行不在 diff 中时返回 None。
+pub fn hunk_at(diff: &str, path: &str, line: Option<usize>) -> Option<String> {
+    let file = split_file_diffs(diff)
+        .into_iter()
+        .find(|file| file.path == path)?;
+    let mut lines = Vec::new();
+    for_each_line(&file.text, |_, text, _, kind| lines.push((text, kind)));
+
+    let header_end = lines
+        .iter()
+        .position(|(text, kind)| text.starts_with("@@") || *kind != LineKind::Meta)
+        .unwrap_or(lines.len());
+    let mut output = String::new();
+    for &(text, kind) in &lines[..header_end] {
+        annotate_line(&mut output, text, kind);
+    }
+    let (start, end) = match line {
+        None => (header_end, lines.len()),
+        Some(line) => {
+            let target = lines
+                .iter()
+                .position(|(_, kind)| kind.new_line() == Some(line))?;
+            let is_hunk_header = |i: &usize| lines[*i].0.starts_with("@@");
+            let hunk = (header_end..=target).rev().find(is_hunk_header)?;
+            let end = (target + 1..lines.len())
+                .find(is_hunk_header)
+                .unwrap_or(lines.len());
+            let whole_file = parse_hunk_starts(lines[hunk].0).is_some_and(|(old, _)| old == 0);
+            if whole_file {
+                let start = target.saturating_sub(SNIPPET_CONTEXT).max(hunk + 1);
+                let end = (target + SNIPPET_CONTEXT + 1).min(end);
+                let first = lines[start].1.new_line().unwrap_or(1);
+                output.push_str(&format!("@@ -0,0 +{},{} @@\n", first, end - start));
+                (start, end)
+            } else {
+                (hunk, end)
+            }
+        }
+    };
+
+    for &(text, kind) in &lines[start..end] {
+        annotate_line(&mut output, text, kind);
+    }
+    Some(output)
+}
+
+/// 新文件中 `line` 前后 `radius` 行的内容（只含 diff 中可见的行，去掉首尾空白）
+///
+/// 用于给问题生成不随行号变化的代码指纹。文件或行不在 diff 中时返回 None。
+pub fn line_context(diff: &str, path: &str, line: usize, radius: usize) -> Option<Vec<String>> {
+    let range = line.saturating_sub(radius)..=line + radius;
+    let mut found = false;
+    let mut context = Vec::new();
+    for_each_line(diff, |_, text, file, kind| {
+        if file != Some(path) {
+            return;
+        }
+        let content = match kind {
+            LineKind::Added(n) | LineKind::Context(n) if range.contains(&n) => {
+                found |= n == line;
+                text.get(1..).unwrap_or("")
+            }
+            _ => return,
+        };
+        context.push(content.trim().to_string());
+    });
+    found.then_some(context)
+}
+
+/// hunk 头中的新旧起始行：`@@ -a,b +c,d @@` -> (a, c)
+fn parse_hunk_starts(header: &str) -> Option<(usize, usize)> {
+    let rest = header.strip_prefix("@@ ")?;
+    let mut parts = rest.split_whitespace();
+    let old = parts
+        .next()?
+        .strip_prefix('-')?
+        .split(',')
+        .next()?
+        .parse()
+        .ok()?;
+    Some((old, parse_hunk_new_start(header)?))
+}
+
+/// 是否像 [`annotate_line_numbers`] 加的行号前缀（5 位右对齐行号或空白，加一个空格）
+fn has_line_number_margin(line: &str) -> bool {
+    line.get(..LINE_NUMBER_WIDTH + 1).is_some_and(|margin| {
+        margin.ends_with(' ') && margin.trim().chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+/// 把模型给出的补丁整理成 git 能应用的单文件 unified diff
+///
+/// 去掉 markdown 代码块标记和误抄的行号前缀，补全 `diff --git` 和 `---`/`+++` 头，
+/// 并按 hunk 内容重新计算行数（模型给的行数经常不对）。
+///
+/// 补丁只能修改 `file`（问题所在、已按审查 diff 校验过的文件）：补丁头写了其他路径，
+/// 或包含多个文件时返回 None，避免模型（或被审查内容注入的指令）借补丁改动任意文件。
+/// 没有 hunk 时也返回 None。
+pub fn normalize_patch(patch: &str, file: &str) -> Option<String> {
+    let mut paths = Vec::new();
+    let mut hunks: Vec<(usize, usize, Vec<&str>)> = Vec::new();
+    for line in patch.lines() {
+        if line.trim_start().starts_with("```") {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            // hunk 之后再出现文件头说明补丁涉及多个文件
+            if !hunks.is_empty() {
+                return None;
+            }
+            for part in rest.split_whitespace() {
+                paths.push(strip_path_prefix(part));
+            }
+        } else if let Some(header) = line.strip_prefix("@@") {
+            let (old, new) = parse_hunk_starts(&format!("@@{}", header))?;
+            hunks.push((old, new, Vec::new()));
+        } else if let Some((_, _, body)) = hunks.last_mut() {
+            if line.starts_with("+++ ") && body.last().is_some_and(|prev| prev.starts_with("--- "))
+            {
+                return None;
+            }
+            body.push(line);
+        } else if let Some(p) = line
+            .strip_prefix("+++ ")
+            .or_else(|| line.strip_prefix("--- "))
+            && p.trim() != "/dev/null"
+        {
+            paths.push(strip_path_prefix(p.trim()));
+        }
+    }
+    if hunks.is_empty() || paths.iter().any(|p| *p != file) {
+        return None;
+    }
+    let path = file;
+
+    // 模型可能照抄了审查 diff 左侧的行号
+    let body_lines = || hunks.iter().flat_map(|(_, _, body)| body.iter());
+    let strip_margin = body_lines().all(|line| line.is_empty() || has_line_number_margin(line))
+        && body_lines()
+            .any(|line| line.bytes().any(|b| b.is_ascii_digit()) && has_line_number_margin(line));
+
+    let mut output = format!("diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n");
+    for (old_start, new_start, body) in &hunks {
+        let mut lines = Vec::with_capacity(body.len());
+        let (mut old_count, mut new_count) = (0, 0);
+        for line in body {
+            let line = if strip_margin {
+                line.get(LINE_NUMBER_WIDTH + 1..).unwrap_or("")
+            } else {
+                line
+            };
+            match line.chars().next() {
+                Some('+') => new_count += 1,
+                Some('-') => old_count += 1,
+                Some('\\') => {}
+                // 空行视为空的上下文行
+                Some(' ') | None => {
+                    old_count += 1;
+                    new_count += 1;
+                }
+                Some(_) => return None,
+            }
+            lines.push(if line.is_empty() { " " } else { line });
+        }
+        // 去掉尾部多余的空上下文行（常见于代码块结尾）
+        while lines.last() == Some(&" ") {
+            lines.pop();
+            old_count -= 1;
+            new_count -= 1;
+        }
+        output.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_count, new_start, new_count
+        ));
+        for line in lines {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    Some(output)
+}
+
+/// 去掉补丁路径的 `a/` / `b/` 前缀
+fn strip_path_prefix(path: &str) -> &str {
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+}
+
+/// diff 中各文件可定位的新文件行号
+#[derive(Debug, Default)]
+pub struct LineIndex {
+    files: BTreeMap<String, FileLines>,
+}
+
+#[derive(Debug, Default)]
+struct FileLines {
+    /// 新增行
+    changed: BTreeSet<usize>,
+    /// diff 中可见的行（新增行和上下文行）
+    visible: BTreeSet<usize>,
+}
+
+impl LineIndex {
+    /// 从 diff 建立索引
+    pub fn from_diff(diff: &str) -> Self {
+        let mut index = Self::default();
+        for_each_line(diff, |_, _, file, kind| {
+            let Some(file) = file else {
+                return;
+            };
+            let lines = index.files.entry(file.to_string()).or_default();
+            match kind {
+                LineKind::Added(n) => {
+                    lines.changed.insert(n);
+                    lines.visible.insert(n);
+                }
+                LineKind::Context(n) => {
+                    lines.visible.insert(n);
+                }
+                LineKind::Meta | LineKind::Removed => {}
+            }
+        });
+        index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// diff 只涉及一个文件时返回其路径
+    pub fn single_file(&self) -> Option<&str> {
+        match self.files.keys().collect::<Vec<_>>().as_slice() {
+            [only] => Some(only.as_str()),
+            _ => None,
+        }
+    }
+
+    /// 匹配 diff 中的文件：路径一致，或唯一的后缀匹配（模型常只写文件名）
+    pub fn resolve_path(&self, file: &str) -> Option<&str> {
+        let file = file.trim();
+        if let Some((path, _)) = self.files.get_key_value(file) {
+            return Some(path);
+        }
+        let file = ["./", "a/", "b/"]
+            .iter()
+            .find_map(|prefix| file.strip_prefix(prefix))
+            .unwrap_or(file);
+        if let Some((path, _)) = self.files.get_key_value(file) {
+            return Some(path);
+        }
+
+        let mut matches = self.files.keys().filter(|path| {
+            path.ends_with(&format!("/{}", file)) || file.ends_with(&format!("/{}", path))
+        });
+        match (matches.next(), matches.next()) {
+            (Some(path), None) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// 校正行号：diff 中可见的行原样返回，否则吸附到附近最近的新增行
+    ///
+    /// 距离超过 [`MAX_SNAP_DISTANCE`] 时返回 None
+    pub fn snap_line(&self, path: &str, line: usize) -> Option<usize> {
+        let lines = self.files.get(path)?;
+        if lines.visible.contains(&line) {
+            return Some(line);
+        }
+        let before = lines.changed.range(..line).next_back();
+        let after = lines.changed.range(line..).next();
+        [before, after]
+            .into_iter()
+            .flatten()
+            .copied()
+            .min_by_key(|n| n.abs_diff(line))
+            .filter(|n| n.abs_diff(line) <= MAX_SNAP_DISTANCE)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +698,284 @@ Binary files a/image.png and b/image.png differ
         assert_eq!(stats.insertions, 0);
         assert_eq!(stats.deletions, 0);
     }
+
+    // === added_lines 测试 ===
+
+    #[test]
+    fn test_parse_hunk_new_start() {
+        assert_eq!(parse_hunk_new_start("@@ -1,3 +10,5 @@ fn main()"), Some(10));
+        assert_eq!(parse_hunk_new_start("@@ -0,0 +1 @@"), Some(1));
+        assert_eq!(parse_hunk_new_start("not a hunk"), None);
+    }
+
+    #[test]
+    fn test_added_lines_tracks_new_line_numbers() {
+        let diff = r#"diff --git a/src/main.rs b/src/main.rs
+index 1234567..abcdefg 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -10,3 +10,4 @@ fn main() {
+ fn main() {
+-    old();
++    new_one();
++    new_two();
+ }
+"#;
+        let lines = added_lines(diff);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].file, "src/main.rs");
+        assert_eq!(lines[0].line, 11);
+        assert_eq!(lines[0].content, "    new_one();");
+        assert_eq!(lines[1].line, 12);
+    }
+
+    #[test]
+    fn test_added_lines_new_file_content() {
+        let diff = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -0,0 +1,2 @@\n+fn a() {}\n+fn b() {}\n";
+        let lines = added_lines(diff);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].file, "src/lib.rs");
+        assert_eq!(lines[1].line, 2);
+        assert_eq!(lines[1].content, "fn b() {}");
+    }
+
+    #[test]
+    fn test_added_lines_header_like_content_in_hunk() {
+        // 新增行内容以 `++ ` 开头，删除行以 `@@` 开头
+        let diff = "diff --git a/notes.md b/notes.md
+--- a/notes.md
++++ b/notes.md
+@@ -1,2 +1,3 @@
+ intro
+-@@ old marker
++++ b/fake.rs
++@@ -1 +1 @@
+\\ No newline at end of file
+";
+        let lines = added_lines(diff);
+        let found: Vec<_> = lines
+            .iter()
+            .map(|l| (l.file.as_str(), l.line, l.content.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("notes.md", 2, "++ b/fake.rs"),
+                ("notes.md", 3, "@@ -1 +1 @@"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_added_lines_embedded_diff_in_new_file() {
+        // 新增的 .patch 文件内容本身是 diff
+        let diff = "diff --git a/fix.patch b/fix.patch\n--- a/fix.patch\n+++ b/fix.patch\n@@ -0,0 +1,5 @@\n+diff --git a/a.rs b/a.rs\n++++ b/a.rs\n+@@ -1 +1 @@\n+-old\n++new\n";
+        let lines = added_lines(diff);
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|l| l.file == "fix.patch"));
+        assert_eq!(lines[0].content, "diff --git a/a.rs b/a.rs");
+        assert_eq!(lines[4].line, 5);
+
+        // hunk 以外的行不是内容
+        assert!(added_lines("--- a.rs\n+++ a.rs\nfn a() {}\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_hunk_lengths() {
+        assert_eq!(
+            parse_hunk_lengths("@@ -10,3 +10,4 @@ fn main() {"),
+            Some((3, 4))
+        );
+        assert_eq!(parse_hunk_lengths("@@ -1 +1,2 @@"), Some((1, 2)));
+        assert_eq!(parse_hunk_lengths("@@ -0,0 +1 @@"), Some((0, 1)));
+        assert_eq!(parse_hunk_lengths("@@ bogus @@"), None);
+    }
+
+    // === split_file_diffs / exclude_paths 测试 ===
+
+    const TWO_FILE_DIFF: &str = "diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1,2 @@
+ fn main() {}
++fn helper() {}
+diff --git a/secrets/prod.env b/secrets/prod.env
+--- /dev/null
++++ b/secrets/prod.env
+@@ -0,0 +1 @@
++TOKEN=abc
+";
+
+    #[test]
+    fn test_split_file_diffs() {
+        let files = split_file_diffs(TWO_FILE_DIFF);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/main.rs");
+        assert_eq!(files[1].path, "secrets/prod.env");
+        assert!(files[1].text.starts_with("diff --git a/secrets/prod.env"));
+        assert_eq!(
+            files.iter().map(|f| f.text.as_str()).collect::<String>(),
+            TWO_FILE_DIFF
+        );
+    }
+
+    #[test]
+    fn test_split_file_diffs_deleted_and_pseudo_diff() {
+        let deleted = "diff --git a/old.rs b/old.rs\n--- a/old.rs\n+++ /dev/null\n-gone\n";
+        assert_eq!(split_file_diffs(deleted)[0].path, "old.rs");
+
+        let pseudo = "--- notes.txt\n+++ notes.txt\nhello\n";
+        assert_eq!(split_file_diffs(pseudo)[0].path, "notes.txt");
+    }
+
+    #[test]
+    fn test_exclude_paths_keeps_file_in_stats() {
+        let (filtered, excluded) =
+            exclude_paths(TWO_FILE_DIFF, |path| path.starts_with("secrets/"));
+
+        assert_eq!(excluded, vec!["secrets/prod.env".to_string()]);
+        assert!(!filtered.contains("TOKEN=abc"));
+        assert!(filtered.contains("omitted by exclusion rules"));
+        assert!(filtered.contains("+fn helper() {}"));
+
+        let stats = parse_diff_stats(&filtered).unwrap();
+        assert_eq!(stats.files_changed.len(), 2);
+    }
+
+    // === 行号标注与校正测试 ===
+
+    const SAMPLE_DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -10,3 +10,4 @@ fn main() {\n context\n-old\n+new\n+more\n tail\n@@ -40,1 +41,2 @@\n keep\n+added\n";
+
+    #[test]
+    fn test_annotate_line_numbers() {
+        let annotated = annotate_line_numbers(SAMPLE_DIFF);
+        assert_eq!(
+            annotated,
+            "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -10,3 +10,4 @@ fn main() {\n   10  context\n      -old\n   11 +new\n   12 +more\n   13  tail\n@@ -40,1 +41,2 @@\n   41  keep\n   42 +added\n"
+        );
+    }
+
+    #[test]
+    fn test_annotate_new_file_diff() {
+        let annotated = annotate_line_numbers("+++ b/a.rs\n@@ -0,0 +1,2 @@\n+fn a() {}\n+\n");
+        assert_eq!(
+            annotated,
+            "+++ b/a.rs\n@@ -0,0 +1,2 @@\n    1 +fn a() {}\n    2 +\n"
+        );
+    }
+
+    #[test]
+    fn test_line_index_resolve_path() {
+        let index = LineIndex::from_diff(SAMPLE_DIFF);
+        assert_eq!(index.resolve_path("src/lib.rs"), Some("src/lib.rs"));
+        assert_eq!(index.resolve_path("b/src/lib.rs"), Some("src/lib.rs"));
+        assert_eq!(index.resolve_path("lib.rs"), Some("src/lib.rs"));
+        assert_eq!(index.resolve_path("src/main.rs"), None);
+        assert_eq!(index.single_file(), Some("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_line_index_snap_line() {
+        let index = LineIndex::from_diff(SAMPLE_DIFF);
+        // 上下文行和新增行保持不变
+        assert_eq!(index.snap_line("src/lib.rs", 10), Some(10));
+        assert_eq!(index.snap_line("src/lib.rs", 12), Some(12));
+        // 不在 diff 中的行吸附到最近的新增行
+        assert_eq!(index.snap_line("src/lib.rs", 15), Some(12));
+        assert_eq!(index.snap_line("src/lib.rs", 38), Some(42));
+        // 太远则丢弃
+        assert_eq!(index.snap_line("src/lib.rs", 200), None);
+        assert_eq!(index.snap_line("src/other.rs", 10), None);
+    }
+
+    #[test]
+    fn test_hunk_at() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,2 +1,2 @@\n one\n+two\n@@ -20,1 +20,2 @@\n twenty\n+added\ndiff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-old\n+new\n";
+        assert_eq!(
+            hunk_at(diff, "a.rs", Some(21)).unwrap(),
+            "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -20,1 +20,2 @@\n   20  twenty\n   21 +added\n"
+        );
+        assert_eq!(
+            hunk_at(diff, "b.rs", None).unwrap(),
+            "diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1 +1 @@\n      -old\n    1 +new\n"
+        );
+        assert_eq!(hunk_at(diff, "a.rs", Some(10)), None);
+        assert_eq!(hunk_at(diff, "c.rs", None), None);
+    }
+
+    #[test]
+    fn test_hunk_at_new_file_window() {
+        let content: String = (1..=30).map(|n| format!("+line{}\n", n)).collect();
+        let diff = format!(
+            "diff --git a/f.txt b/f.txt\n--- a/f.txt\n+++ b/f.txt\n@@ -0,0 +1,30 @@\n{}",
+            content
+        );
+        let snippet = hunk_at(&diff, "f.txt", Some(15)).unwrap();
+        let lines: Vec<_> = snippet.lines().skip(3).collect();
+        assert_eq!(lines[0], "@@ -0,0 +7,17 @@");
+        assert_eq!(lines.len(), 18);
+        assert_eq!(lines[1], "    7 +line7");
+        assert_eq!(lines[17], "   23 +line23");
+
+        let snippet = hunk_at(&diff, "f.txt", Some(2)).unwrap();
+        assert!(snippet.contains("@@ -0,0 +1,10 @@\n    1 +line1\n"));
+    }
+
+    #[test]
+    fn test_normalize_patch_fixes_headers_and_counts() {
+        let patch = "```diff\n@@ -3,2 +3,2 @@\n one\n-let x = y.unwrap();\n+let x = y?;\n\n```\n";
+        assert_eq!(
+            normalize_patch(patch, "src/a.rs").unwrap(),
+            "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -3,2 +3,2 @@\n one\n-let x = y.unwrap();\n+let x = y?;\n"
+        );
+    }
+
+    #[test]
+    fn test_normalize_patch_strips_margin() {
+        let patch = "--- a/src/b.rs\n+++ b/src/b.rs\n@@ -10,1 +10,9 @@\n   10  fn f() {\n      -    old();\n   11 +    new();\n";
+        assert_eq!(
+            normalize_patch(patch, "src/b.rs").unwrap(),
+            "diff --git a/src/b.rs b/src/b.rs\n--- a/src/b.rs\n+++ b/src/b.rs\n@@ -10,2 +10,2 @@\n fn f() {\n-    old();\n+    new();\n"
+        );
+    }
+
+    #[test]
+    fn test_normalize_patch_rejects_unusable() {
+        assert_eq!(normalize_patch("just use ? instead", "a.rs"), None);
+        assert_eq!(
+            normalize_patch("@@ -1 +1 @@\nnot a diff line\n", "a.rs"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize_patch_rejects_other_files() {
+        // 补丁头指向问题以外的文件
+        let other =
+            "--- a/.github/workflows/ci.yml\n+++ b/.github/workflows/ci.yml\n@@ -1 +1 @@\n-a\n+b\n";
+        assert_eq!(normalize_patch(other, "src/a.rs"), None);
+        let renamed = "diff --git a/src/a.rs b/build.rs\n@@ -1 +1 @@\n-a\n+b\n";
+        assert_eq!(normalize_patch(renamed, "src/a.rs"), None);
+
+        // 第二个文件的补丁
+        let multi = "--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-a\n+b\n--- a/build.rs\n+++ b/build.rs\n@@ -1 +1 @@\n-c\n+d\n";
+        assert_eq!(normalize_patch(multi, "src/a.rs"), None);
+        let multi = "diff --git a/src/a.rs b/src/a.rs\n@@ -1 +1 @@\n-a\n+b\ndiff --git a/src/a.rs b/src/a.rs\n@@ -5 +5 @@\n-c\n+d\n";
+        assert_eq!(normalize_patch(multi, "src/a.rs"), None);
+    }
+
+    #[test]
+    fn test_line_context() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,3 +1,4 @@\n one\n-old\n+  two\n three\n+four\n";
+        assert_eq!(
+            line_context(diff, "a.rs", 2, 1).unwrap(),
+            vec!["one", "two", "three"]
+        );
+        assert_eq!(
+            line_context(diff, "a.rs", 4, 1).unwrap(),
+            vec!["three", "four"]
+        );
+        assert_eq!(line_context(diff, "a.rs", 9, 1), None);
+        assert_eq!(line_context(diff, "b.rs", 2, 1), None);
+    }
 }
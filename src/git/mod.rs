@@ -1,6 +1,8 @@
 pub mod commit;
+pub mod commit_lint;
 pub mod diff;
 pub mod repository;
+pub mod secrets;
 
 use crate::error::Result;
 use chrono::{DateTime, Local};
@@ -24,15 +26,33 @@ pub trait GitOperations {
     /// 获取 staged changes 的 diff
     fn get_staged_diff(&self) -> Result<String>;
 
-    /// 获取未提交变更的 diff
+    /// 获取未暂存变更的 diff（index vs 工作区）
     fn get_uncommitted_diff(&self) -> Result<String>;
 
+    /// 获取工作区全部变更的 diff（HEAD vs 工作区，包含未跟踪文件）
+    fn get_worktree_diff(&self) -> Result<String>;
+
     /// 获取指定 commit 的 diff
     fn get_commit_diff(&self, commit_hash: &str) -> Result<String>;
 
     /// 获取 commit 范围的 diff
+    ///
+    /// 支持 `A..B`、`A...B`（以 merge base 为基准）和单个 revision（等价于 `A..HEAD`）
     fn get_range_diff(&self, range: &str) -> Result<String>;
 
+    /// 推断当前分支的比较基准：非同名的上游分支，否则为远端默认分支
+    fn get_branch_base(&self) -> Result<String>;
+
+    /// 计算推送时待发送的 commit 范围（`base..head`），没有新 commit 时返回 None
+    ///
+    /// `remote_sha` 为全零表示远端新建分支，此时以该 remote 已知的所有分支为基准
+    fn get_push_range(
+        &self,
+        local_sha: &str,
+        remote_sha: &str,
+        remote: &str,
+    ) -> Result<Option<String>>;
+
     /// 获取文件或目录的完整内容
     fn get_file_content(&self, path: &str) -> Result<String>;
 
@@ -50,6 +70,9 @@ pub trait GitOperations {
 
     /// 获取 commit 历史
     fn get_commit_history(&self) -> Result<Vec<CommitInfo>>;
+
+    /// 将补丁应用到工作区；`check_only` 为 true 时只检查能否干净应用，不修改文件
+    fn apply_patch(&self, patch: &str, check_only: bool) -> Result<()>;
 }
 
 /// Diff 统计信息
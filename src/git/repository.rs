@@ -21,12 +21,52 @@ impl GitRepository {
     /// * `file_config` - 可选的文件配置，None 则使用默认值
     pub fn open(file_config: Option<&FileConfig>) -> Result<Self> {
         let repo = Repository::open(".")?;
+        Ok(Self::from_repo(repo, file_config))
+    }
+
+    /// 按 git 环境变量（GIT_DIR、GIT_INDEX_FILE 等）打开仓库
+    ///
+    /// 用于 git hook：`git commit -a` 等场景下 git 会通过 GIT_INDEX_FILE
+    /// 指定临时 index，直接打开 "." 会读到错误的暂存内容
+    pub fn open_from_env(file_config: Option<&FileConfig>) -> Result<Self> {
+        let repo = Repository::open_from_env()?;
+        Ok(Self::from_repo(repo, file_config))
+    }
+
+    fn from_repo(repo: Repository, file_config: Option<&FileConfig>) -> Self {
         let max_file_size = file_config
             .map(|c| c.max_size)
             .unwrap_or(DEFAULT_MAX_FILE_SIZE);
-        Ok(Self {
+        Self {
             repo,
             max_file_size,
+        }
+    }
+
+    /// 工作区根目录，bare 仓库返回 None
+    pub fn workdir(&self) -> Option<&std::path::Path> {
+        self.repo.workdir()
+    }
+
+    /// HEAD 指向的 tree，仓库尚无 commit 时返回 None
+    fn head_tree(&self) -> Result<Option<git2::Tree<'_>>> {
+        match self.repo.head() {
+            Ok(head) => Ok(Some(head.peel_to_tree()?)),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 将 pre-push 收到的远端（名称或 URL）映射为已配置的远端名称
+    fn remote_name(&self, remote: &str) -> Option<String> {
+        if self.repo.find_remote(remote).is_ok() {
+            return Some(remote.to_string());
+        }
+        let names = self.repo.remotes().ok()?;
+        names.iter().flatten().find_map(|name| {
+            let configured = self.repo.find_remote(name).ok()?;
+            (configured.url() == Some(remote) || configured.pushurl() == Some(remote))
+                .then(|| name.to_string())
         })
     }
 
@@ -55,18 +95,17 @@ impl GitRepository {
 
 impl GitOperations for GitRepository {
     fn get_staged_diff(&self) -> Result<String> {
-        // 获取 HEAD tree
-        let head = self.repo.head()?;
-        let head_tree = head.peel_to_tree()?;
+        // 获取 HEAD tree（尚无 commit 时为 None，与空树比较）
+        let head_tree = self.head_tree()?;
 
         // 获取 index
         let index = self.repo.index()?;
 
         // 创建 diff（HEAD tree vs index）
         let mut opts = DiffOptions::new();
-        let diff = self
-            .repo
-            .diff_tree_to_index(Some(&head_tree), Some(&index), Some(&mut opts))?;
+        let diff =
+            self.repo
+                .diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut opts))?;
 
         self.diff_to_string(&diff)
     }
@@ -84,6 +123,21 @@ impl GitOperations for GitRepository {
         self.diff_to_string(&diff)
     }
 
+    fn get_worktree_diff(&self) -> Result<String> {
+        let head_tree = self.head_tree()?;
+
+        // 创建 diff（HEAD tree vs workdir），包含未跟踪文件的内容
+        let mut opts = DiffOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        let diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts))?;
+
+        self.diff_to_string(&diff)
+    }
+
     fn get_commit_diff(&self, commit_hash: &str) -> Result<String> {
         // 查找 commit
         let commit = self
@@ -113,20 +167,25 @@ impl GitOperations for GitRepository {
     }
 
     fn get_range_diff(&self, range: &str) -> Result<String> {
-        // 解析范围（如 "main..feature"）
-        let parts: Vec<&str> = range.split("..").collect();
-        if parts.len() != 2 {
-            return Err(GcopError::InvalidInput(format!(
-                "Invalid range format: {}. Expected format: base..head",
-                range
-            )));
-        }
-
-        let base_commit = self.repo.revparse_single(parts[0])?.peel_to_commit()?;
-        let head_commit = self.repo.revparse_single(parts[1])?.peel_to_commit()?;
-
-        let base_tree = base_commit.tree()?;
-        let head_tree = head_commit.tree()?;
+        let spec = parse_range(range)?;
+
+        // peel 到 tree，base 也可以是空树（推送根 commit 时）
+        let head = self.repo.revparse_single(spec.head)?;
+        let base_tree = if spec.merge_base {
+            // A...B：比较 B 与两者的 merge base，忽略 A 上的后续变更
+            let base = self.repo.revparse_single(spec.base)?.peel_to_commit()?;
+            let head = head.peel_to_commit()?;
+            let merge_base = self.repo.merge_base(base.id(), head.id()).map_err(|_| {
+                GcopError::InvalidInput(format!(
+                    "No common ancestor between {} and {}",
+                    spec.base, spec.head
+                ))
+            })?;
+            self.repo.find_commit(merge_base)?.tree()?
+        } else {
+            self.repo.revparse_single(spec.base)?.peel_to_tree()?
+        };
+        let head_tree = head.peel_to_tree()?;
 
         let mut opts = DiffOptions::new();
         let diff =
@@ -136,6 +195,114 @@ impl GitOperations for GitRepository {
         self.diff_to_string(&diff)
     }
 
+    fn get_push_range(
+        &self,
+        local_sha: &str,
+        remote_sha: &str,
+        remote: &str,
+    ) -> Result<Option<String>> {
+        let local = git2::Oid::from_str(local_sha)
+            .map_err(|_| GcopError::InvalidInput(format!("Invalid commit hash: {}", local_sha)))?;
+        // 远端 commit 可能不在本地（如对方已推送新内容），此时按新分支处理
+        let remote = git2::Oid::from_str(remote_sha)
+            .ok()
+            .filter(|oid| !oid.is_zero() && self.repo.find_commit(*oid).is_ok())
+            .ok_or(remote);
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+        revwalk.push(local)?;
+        match remote {
+            Ok(oid) => revwalk.hide(oid)?,
+            Err(remote) => match self.remote_name(remote) {
+                Some(name) => revwalk.hide_glob(&format!("refs/remotes/{}/*", name))?,
+                // 直接推送到 URL 且没有对应的远端时，排除所有远端跟踪分支
+                None => revwalk.hide_glob("refs/remotes/*")?,
+            },
+        }
+
+        let outgoing = revwalk.collect::<std::result::Result<Vec<_>, _>>()?;
+        let Some(oldest) = outgoing.first() else {
+            return Ok(None);
+        };
+
+        let base = match remote {
+            Ok(oid) if self.repo.graph_descendant_of(local, oid)? => oid,
+            _ => {
+                let oldest = self.repo.find_commit(*oldest)?;
+                if oldest.parent_count() > 0 {
+                    oldest.parent_id(0)?
+                } else {
+                    // 根 commit：与空树比较
+                    self.repo.treebuilder(None)?.write()?
+                }
+            }
+        };
+
+        Ok(Some(format!("{}..{}", base, local)))
+    }
+
+    fn get_branch_base(&self) -> Result<String> {
+        let head = self.repo.head()?;
+        let branch = head.is_branch().then(|| head.shorthand()).flatten();
+        let mut remote = "origin".to_string();
+
+        // 1. 跟踪的上游不是同名分支（如 `git checkout -b feat --track origin/main`）时直接使用
+        if let Some(branch) = branch
+            && let Some(refname) = head.name()
+            && let Ok(upstream) = self.repo.branch_upstream_name(refname)
+            && let Some(upstream) = upstream.as_str()
+            && self.repo.find_reference(upstream).is_ok()
+        {
+            if let Ok(name) = self.repo.branch_upstream_remote(refname)
+                && let Some(name) = name.as_str()
+                && name != "."
+            {
+                remote = name.to_string();
+            }
+            let short = upstream
+                .strip_prefix(&format!("refs/remotes/{}/", remote))
+                .or_else(|| upstream.strip_prefix("refs/heads/"))
+                .unwrap_or(upstream);
+            if short != branch {
+                let shorthand = upstream
+                    .strip_prefix("refs/remotes/")
+                    .or_else(|| upstream.strip_prefix("refs/heads/"))
+                    .unwrap_or(upstream);
+                return Ok(shorthand.to_string());
+            }
+        }
+
+        // 2. 远端默认分支（refs/remotes/<remote>/HEAD）
+        if let Ok(reference) = self
+            .repo
+            .find_reference(&format!("refs/remotes/{}/HEAD", remote))
+            && let Some(target) = reference.symbolic_target()
+        {
+            return Ok(target
+                .strip_prefix("refs/remotes/")
+                .unwrap_or(target)
+                .to_string());
+        }
+
+        // 3. 常见的默认分支名
+        let candidates = [
+            format!("{}/main", remote),
+            format!("{}/master", remote),
+            "main".to_string(),
+            "master".to_string(),
+        ];
+        candidates
+            .into_iter()
+            .find(|name| Some(name.as_str()) != branch && self.repo.revparse_single(name).is_ok())
+            .ok_or_else(|| {
+                GcopError::InvalidInput(
+                    "Could not determine the base branch. Pass it explicitly: gcop-rs review branch <base>"
+                        .to_string(),
+                )
+            })
+    }
+
     fn get_file_content(&self, path: &str) -> Result<String> {
         let metadata = std::fs::metadata(path)?;
         if metadata.len() > self.max_file_size {
@@ -176,6 +343,15 @@ impl GitOperations for GitRepository {
         Ok(!diff.trim().is_empty())
     }
 
+    fn apply_patch(&self, patch: &str, check_only: bool) -> Result<()> {
+        let diff = git2::Diff::from_buffer(patch.as_bytes())?;
+        let mut opts = git2::ApplyOptions::new();
+        opts.check(check_only);
+        self.repo
+            .apply(&diff, git2::ApplyLocation::WorkDir, Some(&mut opts))?;
+        Ok(())
+    }
+
     fn get_commit_history(&self) -> Result<Vec<CommitInfo>> {
         let mut revwalk = self.repo.revwalk()?;
         revwalk.push_head()?;
@@ -217,3 +393,132 @@ impl GitOperations for GitRepository {
         Ok(commits)
     }
 }
+
+/// 解析后的 commit 范围
+#[derive(Debug, PartialEq, Eq)]
+struct RangeSpec<'a> {
+    base: &'a str,
+    head: &'a str,
+    /// 三点语法：以两者的 merge base 为基准
+    merge_base: bool,
+}
+
+/// 解析 `A..B`、`A...B` 和单个 revision（等价于 `A..HEAD`），省略的一端为 HEAD
+fn parse_range<'a>(range: &'a str) -> Result<RangeSpec<'a>> {
+    let range = range.trim();
+    let (base, head, merge_base) = if let Some((base, head)) = range.split_once("...") {
+        (base, head, true)
+    } else if let Some((base, head)) = range.split_once("..") {
+        (base, head, false)
+    } else {
+        (range, "", false)
+    };
+
+    if (base.is_empty() && head.is_empty()) || head.contains("..") {
+        return Err(GcopError::InvalidInput(format!(
+            "Invalid range format: {}. Expected base..head, base...head or a single revision",
+            range
+        )));
+    }
+
+    let or_head = |rev: &'a str| if rev.is_empty() { "HEAD" } else { rev };
+    Ok(RangeSpec {
+        base: or_head(base),
+        head: or_head(head),
+        merge_base,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn spec<'a>(base: &'a str, head: &'a str, merge_base: bool) -> RangeSpec<'a> {
+        RangeSpec {
+            base,
+            head,
+            merge_base,
+        }
+    }
+
+    #[test]
+    fn test_parse_range_two_and_three_dots() {
+        assert_eq!(
+            parse_range("main..feature").unwrap(),
+            spec("main", "feature", false)
+        );
+        assert_eq!(
+            parse_range("main...feature").unwrap(),
+            spec("main", "feature", true)
+        );
+    }
+
+    #[test]
+    fn test_parse_range_shorthand() {
+        assert_eq!(parse_range("main").unwrap(), spec("main", "HEAD", false));
+        assert_eq!(parse_range("main..").unwrap(), spec("main", "HEAD", false));
+        assert_eq!(
+            parse_range("...topic").unwrap(),
+            spec("HEAD", "topic", true)
+        );
+    }
+
+    #[test]
+    fn test_parse_range_invalid() {
+        assert!(parse_range("").is_err());
+        assert!(parse_range("..").is_err());
+        assert!(parse_range("a..b..c").is_err());
+    }
+
+    #[test]
+    fn test_apply_patch_check_then_apply() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = GitRepository::from_repo(Repository::init(dir.path()).unwrap(), None);
+        let file = dir.path().join("a.rs");
+        std::fs::write(&file, "one\ntwo\nthree\n").unwrap();
+
+        let patch = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,3 +1,3 @@\n one\n-two\n+TWO\n three\n";
+        repo.apply_patch(patch, true).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "one\ntwo\nthree\n");
+
+        repo.apply_patch(patch, false).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "one\nTWO\nthree\n");
+
+        // 已应用过的补丁不能再次干净应用
+        assert!(repo.apply_patch(patch, true).is_err());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "one\nTWO\nthree\n");
+    }
+
+    #[test]
+    fn test_get_push_range_new_branch_by_url() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let git = Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let commit = |message: &str, parents: &[&git2::Commit]| {
+            let tree = git.treebuilder(None).unwrap().write().unwrap();
+            let tree = git.find_tree(tree).unwrap();
+            git.commit(None, &sig, &sig, message, &tree, parents)
+                .unwrap()
+        };
+        let pushed = commit("pushed", &[]);
+        let local = commit("local", &[&git.find_commit(pushed).unwrap()]);
+        git.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        git.reference("refs/remotes/origin/main", pushed, false, "")
+            .unwrap();
+        let repo = GitRepository::from_repo(git, None);
+
+        let zero = git2::Oid::zero().to_string();
+        let expected = Some(format!("{}..{}", pushed, local));
+        for remote in ["origin", "https://example.com/repo.git", "/tmp/unknown.git"] {
+            assert_eq!(
+                repo.get_push_range(&local.to_string(), &zero, remote)
+                    .unwrap(),
+                expected,
+                "remote: {}",
+                remote
+            );
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! 本地 LLM 响应缓存
+//!
+//! 以 provider、模型、参数和完整 prompt 的哈希作为 key，
+//! 将响应保存在缓存目录下，避免对相同输入重复计费。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use sha2::{Digest, Sha256};
+
+use crate::config::{self, CacheConfig};
+use crate::error::{GcopError, Result};
+
+/// 缓存文件扩展名
+const CACHE_FILE_EXTENSION: &str = "cache";
+
+/// 缓存统计信息
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub total_bytes: u64,
+}
+
+/// 基于文件系统的响应缓存
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    max_size: u64,
+}
+
+impl ResponseCache {
+    /// 在指定目录创建缓存
+    pub fn new(dir: impl Into<PathBuf>, max_size: u64) -> Self {
+        Self {
+            dir: dir.into(),
+            max_size,
+        }
+    }
+
+    /// 使用默认缓存目录（<cache_dir>/responses）创建缓存
+    pub fn from_config(cache_config: &CacheConfig) -> Result<Self> {
+        let dir = config::get_cache_dir()
+            .ok_or_else(|| GcopError::Config("Failed to determine cache directory".to_string()))?
+            .join("responses");
+        Ok(Self::new(dir, cache_config.max_size))
+    }
+
+    /// 缓存目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 由若干输入片段计算缓存 key（SHA-256 十六进制）
+    ///
+    /// 片段之间使用 NUL 分隔，避免 ("ab", "c") 与 ("a", "bc") 冲突
+    pub fn key(parts: &[&str]) -> String {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            hasher.update(part.as_bytes());
+            hasher.update([0u8]);
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// 读取缓存，命中时刷新修改时间（用于 LRU 淘汰）
+    pub fn get(&self, key: &str) -> Option<String> {
+        let path = self.entry_path(key);
+        let content = fs::read_to_string(&path).ok()?;
+        if let Ok(file) = fs::File::options().append(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        tracing::debug!("Response cache hit: {}", key);
+        Some(content)
+    }
+
+    /// 写入缓存，并在超出大小限制时淘汰旧条目
+    pub fn put(&self, key: &str, value: &str) -> Result<()> {
+        if value.len() as u64 > self.max_size {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.entry_path(key), value)?;
+        self.prune()?;
+        Ok(())
+    }
+
+    /// 清空缓存，返回被删除的条目统计
+    pub fn clear(&self) -> Result<CacheStats> {
+        let mut stats = CacheStats::default();
+        for (path, size, _) in self.entries()? {
+            fs::remove_file(path)?;
+            stats.entries += 1;
+            stats.total_bytes += size;
+        }
+        Ok(stats)
+    }
+
+    /// 当前缓存统计
+    pub fn stats(&self) -> Result<CacheStats> {
+        let entries = self.entries()?;
+        Ok(CacheStats {
+            entries: entries.len(),
+            total_bytes: entries.iter().map(|(_, size, _)| size).sum(),
+        })
+    }
+
+    /// 淘汰最久未使用的条目，直到总大小不超过限制
+    fn prune(&self) -> Result<()> {
+        let mut entries = self.entries()?;
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        if total <= self.max_size {
+            return Ok(());
+        }
+
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in entries {
+            if total <= self.max_size {
+                break;
+            }
+            fs::remove_file(&path)?;
+            total -= size;
+            tracing::debug!("Evicted cache entry: {}", path.display());
+        }
+        Ok(())
+    }
+
+    /// 列出所有缓存条目 (路径, 大小, 修改时间)
+    fn entries(&self) -> Result<Vec<(PathBuf, u64, SystemTime)>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(CACHE_FILE_EXTENSION) {
+                continue;
+            }
+            let metadata = fs::metadata(&path)?;
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            entries.push((path, metadata.len(), modified));
+        }
+        Ok(entries)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", key, CACHE_FILE_EXTENSION))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_key_is_stable_and_separated() {
+        let a = ResponseCache::key(&["claude", "model", "prompt"]);
+        assert_eq!(a, ResponseCache::key(&["claude", "model", "prompt"]));
+        assert_eq!(a.len(), 64);
+        assert_ne!(
+            ResponseCache::key(&["ab", "c"]),
+            ResponseCache::key(&["a", "bc"])
+        );
+    }
+
+    #[test]
+    fn test_put_get_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let cache = ResponseCache::new(dir.path(), 1024);
+
+        assert_eq!(cache.get("missing"), None);
+        cache.put("k1", "feat: cached").unwrap();
+        assert_eq!(cache.get("k1"), Some("feat: cached".to_string()));
+    }
+
+    #[test]
+    fn test_prune_evicts_oldest_entries() {
+        let dir = TempDir::new().unwrap();
+        let cache = ResponseCache::new(dir.path(), 10);
+
+        cache.put("old", "aaaaaa").unwrap();
+        let old_path = cache.entry_path("old");
+        let file = fs::File::options().append(true).open(&old_path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(60))
+            .unwrap();
+
+        cache.put("new", "bbbbbb").unwrap();
+
+        assert_eq!(cache.get("old"), None);
+        assert_eq!(cache.get("new"), Some("bbbbbb".to_string()));
+    }
+
+    #[test]
+    fn test_oversized_value_is_not_cached() {
+        let dir = TempDir::new().unwrap();
+        let cache = ResponseCache::new(dir.path(), 4);
+
+        cache.put("big", "too large").unwrap();
+        assert_eq!(cache.get("big"), None);
+    }
+
+    #[test]
+    fn test_clear_and_stats() {
+        let dir = TempDir::new().unwrap();
+        let cache = ResponseCache::new(dir.path().join("responses"), 1024);
+
+        assert_eq!(cache.stats().unwrap(), CacheStats::default());
+        cache.put("a", "123").unwrap();
+        cache.put("b", "45").unwrap();
+        assert_eq!(
+            cache.stats().unwrap(),
+            CacheStats {
+                entries: 2,
+                total_bytes: 5
+            }
+        );
+
+        let cleared = cache.clear().unwrap();
+        assert_eq!(cleared.entries, 2);
+        assert_eq!(cache.stats().unwrap().entries, 0);
+    }
+}
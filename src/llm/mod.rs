@@ -1,6 +1,9 @@
+pub mod cache;
 pub mod message;
 pub mod prompt;
 pub mod provider;
+pub mod tokens;
+pub mod usage;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -51,6 +54,18 @@ pub trait LLMProvider: Send + Sync {
     /// 验证配置
     async fn validate(&self) -> Result<()>;
 
+    /// 发送任意 prompt 并返回原始文本（用于改写建议、追问等）
+    async fn complete(
+        &self,
+        _prompt: &str,
+        _spinner: Option<&crate::ui::Spinner>,
+    ) -> Result<String> {
+        Err(crate::error::GcopError::Llm(format!(
+            "{} does not support free-form prompts",
+            self.name()
+        )))
+    }
+
     /// 是否支持流式响应
     fn supports_streaming(&self) -> bool {
         false
@@ -98,6 +113,8 @@ pub struct CommitContext {
 #[derive(Debug, Clone)]
 pub enum ReviewType {
     UncommittedChanges,
+    StagedChanges,
+    WorkingTree,
     SingleCommit(String),
     CommitRange(String),
     FileOrDir(String),
@@ -120,13 +137,68 @@ pub struct ReviewIssue {
     pub file: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line: Option<usize>,
+    /// 建议的修复（unified diff），只保留能干净应用到工作区的补丁
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch: Option<String>,
 }
 
 /// 问题严重性
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum IssueSeverity {
     Critical,
     Warning,
     Info,
 }
+
+impl IssueSeverity {
+    /// 从配置字符串解析（"critical" | "warning" | "info"），未知值按 info 处理
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "critical" => IssueSeverity::Critical,
+            "warning" => IssueSeverity::Warning,
+            _ => IssueSeverity::Info,
+        }
+    }
+
+    /// 严重程度，数值越小越严重
+    pub fn level(&self) -> u8 {
+        match self {
+            IssueSeverity::Critical => 0,
+            IssueSeverity::Warning => 1,
+            IssueSeverity::Info => 2,
+        }
+    }
+
+    /// 是否达到给定的阈值（与阈值同级或更严重）
+    pub fn meets(&self, threshold: IssueSeverity) -> bool {
+        self.level() <= threshold.level()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_severity_from_config() {
+        assert_eq!(
+            IssueSeverity::from_config("critical"),
+            IssueSeverity::Critical
+        );
+        assert_eq!(
+            IssueSeverity::from_config("warning"),
+            IssueSeverity::Warning
+        );
+        assert_eq!(IssueSeverity::from_config("info"), IssueSeverity::Info);
+        assert_eq!(IssueSeverity::from_config("typo"), IssueSeverity::Info);
+    }
+
+    #[test]
+    fn test_issue_severity_meets_threshold() {
+        assert!(IssueSeverity::Critical.meets(IssueSeverity::Warning));
+        assert!(IssueSeverity::Warning.meets(IssueSeverity::Warning));
+        assert!(!IssueSeverity::Info.meets(IssueSeverity::Warning));
+        assert!(IssueSeverity::Info.meets(IssueSeverity::Info));
+    }
+}
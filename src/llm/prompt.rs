@@ -1,4 +1,4 @@
-use crate::llm::{CommitContext, ReviewType};
+use crate::llm::{CommitContext, ReviewIssue, ReviewType};
 
 /// 默认的 commit prompt 模板
 const DEFAULT_COMMIT_PROMPT: &str = r#"You are an expert software engineer reviewing a git diff to generate a concise, informative commit message.
@@ -51,8 +51,9 @@ const DEFAULT_JSON_FORMAT: &str = r#"## Output Format:
         {{
         "severity": "critical" | "warning" | "info",
         "description": "Issue description",
-        "file": "filename (if applicable)",
-        "line": line_number (if applicable)
+        "file": "path as shown in the diff header (if applicable)",
+        "line": new-file line number shown in the left margin (if applicable),
+        "patch": "unified diff that fixes the issue (optional)"
         }}
     ],
     "suggestions": [
@@ -60,8 +61,96 @@ const DEFAULT_JSON_FORMAT: &str = r#"## Output Format:
     ]
     }}
 
+    Each line in the diff is prefixed with its line number in the new file; removed lines have no number.
+    Only report line numbers that appear in the diff.
+    Include "patch" only for small, self-contained fixes you are confident about. It must be a unified diff against the new version of the file, with "--- a/<path>" and "+++ b/<path>" headers and hunk context copied exactly (without the line-number margin).
     If no issues found, return empty issues array but provide constructive suggestions."#;
 
+/// commit message 改写 prompt 模板
+const DEFAULT_REWRITE_PROMPT: &str = r#"Rewrite the following git commit message so that it follows the project's commit convention. Keep the original meaning and do not invent changes.
+
+    ## Convention:
+    {rules}
+
+    ## Problems Found:
+    {violations}
+
+    ## Original Message:
+    ```
+    {message}
+    ```
+
+    Output only the rewritten commit message, no explanations."#;
+
+/// 构建 commit message 改写的 prompt
+pub fn build_rewrite_prompt(message: &str, rules: &[String], violations: &[String]) -> String {
+    let bullets = |items: &[String]| {
+        items
+            .iter()
+            .map(|item| format!("- {}", item))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    DEFAULT_REWRITE_PROMPT
+        .replace("{rules}", &bullets(rules))
+        .replace("{violations}", &bullets(violations))
+        .replace("{message}", message)
+}
+
+/// 审查问题追问 prompt 模板
+const DEFAULT_FOLLOWUP_PROMPT: &str = r#"You reviewed a code change and reported the issue below. Answer the developer's question about this issue. Be concise and concrete, and say so if the issue turns out to be a false positive.
+
+    ## Issue:
+    [{severity}] {description}
+    Location: {location}
+
+    ## Relevant Code:
+    ```
+    {code}
+    ```
+    {history}
+    ## Question:
+    {question}
+
+    Answer in plain text, not JSON."#;
+
+/// 构建审查问题追问的 prompt
+///
+/// `history` 为同一问题此前的 (问题, 回答)，让模型延续上下文
+pub fn build_followup_prompt(
+    issue: &ReviewIssue,
+    code: &str,
+    history: &[(String, String)],
+    question: &str,
+) -> String {
+    let location = match (issue.file.as_deref(), issue.line) {
+        (Some(file), Some(line)) => format!("{}:{}", file, line),
+        (Some(file), None) => file.to_string(),
+        _ => "not specified".to_string(),
+    };
+    let history = if history.is_empty() {
+        String::new()
+    } else {
+        let turns: Vec<String> = history
+            .iter()
+            .map(|(q, a)| format!("Q: {}\nA: {}", q, a))
+            .collect();
+        format!("\n## Earlier Questions:\n{}\n", turns.join("\n\n"))
+    };
+
+    DEFAULT_FOLLOWUP_PROMPT
+        .replace(
+            "{severity}",
+            &format!("{:?}", issue.severity).to_uppercase(),
+        )
+        .replace("{description}", &issue.description)
+        .replace("{location}", &location)
+        .replace("{code}", code)
+        .replace("{history}", &history)
+        .replace("{question}", question)
+}
+
 /// 构建 commit message 生成的 prompt
 pub fn build_commit_prompt(
     diff: &str,
@@ -108,6 +197,19 @@ pub fn build_commit_prompt(
     prompt
 }
 
+/// 在审查 prompt 模板后追加仓库的审查规则，返回新的模板
+///
+/// 结果作为 custom_prompt 传给 provider，规则因此也参与响应缓存的 key
+pub fn with_project_rules(custom_template: Option<&str>, rules: &str) -> String {
+    let template = custom_template.unwrap_or(DEFAULT_REVIEW_PROMPT);
+    // 规则是普通文本，其中的 {diff} 不应被替换成 diff
+    format!(
+        "{}\n\n## Project Review Rules:\n{}",
+        template,
+        rules.replace("{diff}", "{ diff }")
+    )
+}
+
 /// 构建代码审查的 prompt
 pub fn build_review_prompt(
     diff: &str,
@@ -312,4 +414,58 @@ mod tests {
         assert!(result.contains("Special instructions: code"));
         assert!(result.contains("Extra notes here"));
     }
+
+    // === build_rewrite_prompt 测试 ===
+
+    #[test]
+    fn test_build_rewrite_prompt() {
+        let result = build_rewrite_prompt(
+            "Updated stuff.",
+            &["Allowed types: feat, fix".to_string()],
+            &["First line must look like 'type(scope): subject'".to_string()],
+        );
+
+        assert!(result.contains("- Allowed types: feat, fix"));
+        assert!(result.contains("- First line must look like"));
+        assert!(result.contains("Updated stuff."));
+        assert!(!result.contains("{message}"));
+    }
+
+    #[test]
+    fn test_build_followup_prompt() {
+        let issue = ReviewIssue {
+            severity: crate::llm::IssueSeverity::Warning,
+            description: "Unchecked unwrap".to_string(),
+            file: Some("src/main.rs".to_string()),
+            line: Some(7),
+            patch: None,
+        };
+        let result = build_followup_prompt(
+            &issue,
+            "    7 +let x = y.unwrap();",
+            &[("Why?".to_string(), "It can panic.".to_string())],
+            "How do I fix it?",
+        );
+
+        assert!(result.contains("[WARNING] Unchecked unwrap"));
+        assert!(result.contains("Location: src/main.rs:7"));
+        assert!(result.contains("let x = y.unwrap();"));
+        assert!(result.contains("Q: Why?\nA: It can panic."));
+        assert!(result.contains("How do I fix it?"));
+        assert!(!result.contains("{history}"));
+    }
+
+    #[test]
+    fn test_with_project_rules() {
+        let template = with_project_rules(None, "Migrations must be reversible. {diff}");
+        let result =
+            build_review_prompt("my_diff", &ReviewType::UncommittedChanges, Some(&template));
+
+        assert!(result.contains("expert code reviewer"));
+        assert!(
+            result.contains("## Project Review Rules:\nMigrations must be reversible. { diff }")
+        );
+        assert_eq!(result.matches("my_diff").count(), 1);
+        assert!(result.find("Project Review Rules") < result.find("Output Format"));
+    }
 }
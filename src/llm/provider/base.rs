@@ -11,6 +11,7 @@ use crate::config::ProviderConfig;
 use crate::error::{GcopError, Result};
 use crate::llm::ReviewResult;
 
+use super::credentials::resolve_api_key;
 use super::utils::complete_endpoint;
 
 /// 默认 max_tokens
@@ -207,7 +208,7 @@ where
     }
 }
 
-/// 提取 API key（配置优先，环境变量 fallback）
+/// 提取 API key（`api_key` > `api_key_file` > `api_key_cmd` > 环境变量）
 ///
 /// # Arguments
 /// * `config` - Provider 配置
@@ -218,16 +219,16 @@ pub fn extract_api_key(
     env_var: &str,
     provider_name: &str,
 ) -> Result<String> {
-    config
-        .api_key
-        .clone()
-        .or_else(|| std::env::var(env_var).ok())
-        .ok_or_else(|| {
-            GcopError::Config(format!(
-                "{} API key not found. Set api_key in config.toml or {} environment variable",
-                provider_name, env_var
-            ))
-        })
+    match resolve_api_key(config, Some(env_var))? {
+        Some((key, source)) => {
+            tracing::debug!("{} API key from {}", provider_name, source);
+            Ok(key)
+        }
+        None => Err(GcopError::Config(format!(
+            "{} API key not found. Set api_key, api_key_file or api_key_cmd in config.toml, or the {} environment variable",
+            provider_name, env_var
+        ))),
+    }
 }
 
 /// 构建完整 endpoint
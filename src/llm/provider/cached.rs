@@ -0,0 +1,324 @@
+//! 带本地缓存的 Provider 包装
+//!
+//! 缓存 key 由 provider 指纹（名称、API 风格、endpoint、模型、参数）
+//! 和实际发送的 prompt 组成，输入完全相同时直接返回缓存结果。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+
+use crate::config::ProviderConfig;
+use crate::error::Result;
+use crate::llm::cache::ResponseCache;
+use crate::llm::prompt::{build_commit_prompt, build_review_prompt};
+use crate::llm::{CommitContext, LLMProvider, ReviewResult, ReviewType, StreamChunk, StreamHandle};
+
+use super::base::{get_max_tokens_optional, get_temperature_optional};
+
+/// 带缓存的 LLM Provider
+pub struct CachedProvider {
+    inner: Arc<dyn LLMProvider>,
+    cache: ResponseCache,
+    fingerprint: String,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Arc<dyn LLMProvider>, cache: ResponseCache, fingerprint: String) -> Self {
+        Self {
+            inner,
+            cache,
+            fingerprint,
+        }
+    }
+
+    fn commit_key(&self, diff: &str, context: &CommitContext) -> String {
+        let prompt = build_commit_prompt(diff, context, context.custom_prompt.as_deref());
+        ResponseCache::key(&[&self.fingerprint, "commit", &prompt])
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        if let Err(e) = self.cache.put(key, value) {
+            tracing::warn!("Failed to write response cache: {}", e);
+        }
+    }
+}
+
+/// 计算 provider 指纹，任何影响输出的配置变化都会产生不同的 key
+pub fn provider_fingerprint(name: &str, config: &ProviderConfig) -> String {
+    format!(
+        "{}|{}|{}|{}|{:?}|{:?}",
+        name,
+        config.api_style.as_deref().unwrap_or(name),
+        config.endpoint.as_deref().unwrap_or_default(),
+        config.model,
+        get_max_tokens_optional(config),
+        get_temperature_optional(config),
+    )
+}
+
+#[async_trait]
+impl LLMProvider for CachedProvider {
+    async fn generate_commit_message(
+        &self,
+        diff: &str,
+        context: Option<CommitContext>,
+        spinner: Option<&crate::ui::Spinner>,
+    ) -> Result<String> {
+        let ctx = context.unwrap_or_default();
+        let key = self.commit_key(diff, &ctx);
+        if let Some(message) = self.cache.get(&key) {
+            return Ok(message);
+        }
+
+        let message = self
+            .inner
+            .generate_commit_message(diff, Some(ctx), spinner)
+            .await?;
+        self.store(&key, &message);
+        Ok(message)
+    }
+
+    async fn review_code(
+        &self,
+        diff: &str,
+        review_type: ReviewType,
+        custom_prompt: Option<&str>,
+        spinner: Option<&crate::ui::Spinner>,
+    ) -> Result<ReviewResult> {
+        let prompt = build_review_prompt(diff, &review_type, custom_prompt);
+        let key = ResponseCache::key(&[&self.fingerprint, "review", &prompt]);
+
+        if let Some(cached) = self.cache.get(&key) {
+            match serde_json::from_str(&cached) {
+                Ok(result) => return Ok(result),
+                Err(e) => tracing::debug!("Ignoring corrupt cache entry {}: {}", key, e),
+            }
+        }
+
+        let result = self
+            .inner
+            .review_code(diff, review_type, custom_prompt, spinner)
+            .await?;
+        self.store(&key, &serde_json::to_string(&result)?);
+        Ok(result)
+    }
+
+    async fn complete(&self, prompt: &str, spinner: Option<&crate::ui::Spinner>) -> Result<String> {
+        self.inner.complete(prompt, spinner).await
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn validate(&self) -> Result<()> {
+        self.inner.validate().await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    async fn generate_commit_message_streaming(
+        &self,
+        diff: &str,
+        context: Option<CommitContext>,
+    ) -> Result<StreamHandle> {
+        let ctx = context.unwrap_or_default();
+        let key = self.commit_key(diff, &ctx);
+        let (tx, rx) = mpsc::channel(64);
+
+        if let Some(message) = self.cache.get(&key) {
+            let _ = tx.send(StreamChunk::Delta(message)).await;
+            let _ = tx.send(StreamChunk::Done).await;
+            return Ok(StreamHandle { receiver: rx });
+        }
+
+        let mut upstream = self
+            .inner
+            .generate_commit_message_streaming(diff, Some(ctx))
+            .await?;
+        let cache = self.cache.clone();
+
+        // 转发流式数据，完整结束后写入缓存
+        tokio::spawn(async move {
+            let mut buffer = String::new();
+            while let Some(chunk) = upstream.receiver.recv().await {
+                let done = matches!(chunk, StreamChunk::Done);
+                let failed = matches!(chunk, StreamChunk::Error(_));
+                if let StreamChunk::Delta(text) = &chunk {
+                    buffer.push_str(text);
+                }
+                if tx.send(chunk).await.is_err() || failed {
+                    return;
+                }
+                if done {
+                    break;
+                }
+            }
+            if !buffer.is_empty()
+                && let Err(e) = cache.put(&key, &buffer)
+            {
+                tracing::warn!("Failed to write response cache: {}", e);
+            }
+        });
+
+        Ok(StreamHandle { receiver: rx })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::IssueSeverity;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LLMProvider for CountingProvider {
+        async fn generate_commit_message(
+            &self,
+            _diff: &str,
+            _context: Option<CommitContext>,
+            _spinner: Option<&crate::ui::Spinner>,
+        ) -> Result<String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("feat: message {}", n))
+        }
+
+        async fn review_code(
+            &self,
+            _diff: &str,
+            _review_type: ReviewType,
+            _custom_prompt: Option<&str>,
+            _spinner: Option<&crate::ui::Spinner>,
+        ) -> Result<ReviewResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ReviewResult {
+                summary: "ok".to_string(),
+                issues: vec![crate::llm::ReviewIssue {
+                    severity: IssueSeverity::Warning,
+                    description: "nit".to_string(),
+                    file: None,
+                    line: None,
+                    patch: None,
+                }],
+                suggestions: vec![],
+            })
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        async fn validate(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn cached(dir: &TempDir) -> (Arc<CountingProvider>, CachedProvider) {
+        let inner = Arc::new(CountingProvider {
+            calls: AtomicUsize::new(0),
+        });
+        let provider = CachedProvider::new(
+            inner.clone(),
+            ResponseCache::new(dir.path(), 1024 * 1024),
+            "counting|model".to_string(),
+        );
+        (inner, provider)
+    }
+
+    #[tokio::test]
+    async fn test_review_is_served_from_cache() {
+        let dir = TempDir::new().unwrap();
+        let (inner, provider) = cached(&dir);
+
+        let review_type = ReviewType::SingleCommit("abc".to_string());
+        let first = provider
+            .review_code("diff", review_type.clone(), None, None)
+            .await
+            .unwrap();
+        let second = provider
+            .review_code("diff", review_type, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.issues.len(), second.issues.len());
+    }
+
+    #[tokio::test]
+    async fn test_different_prompt_misses_cache() {
+        let dir = TempDir::new().unwrap();
+        let (inner, provider) = cached(&dir);
+
+        provider
+            .generate_commit_message("diff a", None, None)
+            .await
+            .unwrap();
+        provider
+            .generate_commit_message("diff b", None, None)
+            .await
+            .unwrap();
+        let repeat = provider
+            .generate_commit_message("diff a", None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(repeat, "feat: message 0");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_result_is_cached() {
+        let dir = TempDir::new().unwrap();
+        let (inner, provider) = cached(&dir);
+
+        for _ in 0..2 {
+            let mut handle = provider
+                .generate_commit_message_streaming("diff", None)
+                .await
+                .unwrap();
+            let mut text = String::new();
+            while let Some(chunk) = handle.receiver.recv().await {
+                match chunk {
+                    StreamChunk::Delta(t) => text.push_str(&t),
+                    StreamChunk::Done => break,
+                    StreamChunk::Error(e) => panic!("{}", e),
+                }
+            }
+            assert_eq!(text, "feat: message 0");
+            // 等待后台任务写入缓存
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_provider_fingerprint_changes_with_params() {
+        let mut config = ProviderConfig {
+            api_style: None,
+            endpoint: None,
+            api_key: Some("secret".to_string()),
+            api_key_file: None,
+            api_key_cmd: None,
+            model: "m1".to_string(),
+            max_tokens: None,
+            temperature: Some(0.3),
+            extra: HashMap::new(),
+        };
+        let a = provider_fingerprint("claude", &config);
+        config.temperature = Some(0.7);
+        let b = provider_fingerprint("claude", &config);
+
+        assert_ne!(a, b);
+        assert!(!a.contains("secret"));
+    }
+}
@@ -9,11 +9,13 @@ use super::base::{
 use super::utils::{CLAUDE_API_SUFFIX, DEFAULT_CLAUDE_BASE};
 use crate::config::{NetworkConfig, ProviderConfig};
 use crate::error::{GcopError, Result};
+use crate::llm::usage::{TokenUsage, UsageLog, record_usage};
 use crate::llm::{CommitContext, LLMProvider, ReviewResult, ReviewType};
 
 /// Claude API Provider
 pub struct ClaudeProvider {
     client: Client,
+    provider_name: String,
     api_key: String,
     endpoint: String,
     model: String,
@@ -21,6 +23,7 @@ pub struct ClaudeProvider {
     temperature: f32,
     max_retries: usize,
     retry_delay_ms: u64,
+    usage_log: Option<UsageLog>,
 }
 
 #[derive(Serialize)]
@@ -40,6 +43,14 @@ struct MessagePayload {
 #[derive(Deserialize)]
 struct ClaudeResponse {
     content: Vec<ContentBlock>,
+    #[serde(default)]
+    usage: Option<ClaudeUsage>,
+}
+
+#[derive(Deserialize)]
+struct ClaudeUsage {
+    input_tokens: u64,
+    output_tokens: u64,
 }
 
 #[derive(Deserialize)]
@@ -52,8 +63,9 @@ struct ContentBlock {
 impl ClaudeProvider {
     pub fn new(
         config: &ProviderConfig,
-        _provider_name: &str,
+        provider_name: &str,
         network_config: &NetworkConfig,
+        usage_log: Option<UsageLog>,
     ) -> Result<Self> {
         let api_key = extract_api_key(config, "ANTHROPIC_API_KEY", "Claude")?;
         let endpoint = build_endpoint(config, DEFAULT_CLAUDE_BASE, CLAUDE_API_SUFFIX);
@@ -63,6 +75,7 @@ impl ClaudeProvider {
 
         Ok(Self {
             client: super::create_http_client(network_config)?,
+            provider_name: provider_name.to_string(),
             api_key,
             endpoint,
             model,
@@ -70,6 +83,7 @@ impl ClaudeProvider {
             temperature,
             max_retries: network_config.max_retries,
             retry_delay_ms: network_config.retry_delay_ms,
+            usage_log,
         })
     }
 
@@ -106,6 +120,16 @@ impl ClaudeProvider {
         )
         .await?;
 
+        record_usage(
+            self.usage_log.as_ref(),
+            &self.provider_name,
+            &self.model,
+            response.usage.map(|u| TokenUsage {
+                input_tokens: u.input_tokens,
+                output_tokens: u.output_tokens,
+            }),
+        );
+
         let text = response
             .content
             .into_iter()
@@ -157,6 +181,10 @@ impl LLMProvider for ClaudeProvider {
         parse_review_response(&response)
     }
 
+    async fn complete(&self, prompt: &str, spinner: Option<&crate::ui::Spinner>) -> Result<String> {
+        self.call_api(prompt, spinner).await
+    }
+
     fn name(&self) -> &str {
         "claude"
     }
@@ -0,0 +1,214 @@
+//! API key 来源
+//!
+//! 按以下顺序查找 API key：配置中的 `api_key`、`api_key_file` 指向的文件、
+//! `api_key_cmd` 命令的输出，最后是 provider 对应的环境变量。
+//! 命令在同一进程中只执行一次，结果缓存在内存中。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::process::Command;
+use std::sync::{LazyLock, Mutex};
+
+use crate::config::ProviderConfig;
+use crate::error::{GcopError, Result};
+
+/// `api_key_cmd` 的输出缓存，key 为命令
+static COMMAND_CACHE: LazyLock<Mutex<HashMap<String, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// API key 的来源（不包含 key 本身）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeySource {
+    Config,
+    File(String),
+    Command(String),
+    Env(String),
+}
+
+impl fmt::Display for ApiKeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiKeySource::Config => write!(f, "api_key in config"),
+            ApiKeySource::File(path) => write!(f, "api_key_file {}", path),
+            ApiKeySource::Command(cmd) => write!(f, "api_key_cmd `{}`", cmd),
+            ApiKeySource::Env(var) => write!(f, "environment variable {}", var),
+        }
+    }
+}
+
+/// 各 API 风格读取的环境变量，不需要 key 的风格返回 None
+pub fn api_key_env_var(api_style: &str) -> Option<&'static str> {
+    match api_style {
+        "claude" => Some("ANTHROPIC_API_KEY"),
+        "openai" => Some("OPENAI_API_KEY"),
+        _ => None,
+    }
+}
+
+/// 查找 API key 及其来源，都没有配置时返回 None
+///
+/// 文件不可读、命令失败或输出为空时报错，而不是继续尝试下一个来源
+pub fn resolve_api_key(
+    config: &ProviderConfig,
+    env_var: Option<&str>,
+) -> Result<Option<(String, ApiKeySource)>> {
+    if let Some(key) = &config.api_key {
+        return Ok(Some((key.clone(), ApiKeySource::Config)));
+    }
+    if let Some(path) = &config.api_key_file {
+        return read_key_file(path).map(|key| Some((key, ApiKeySource::File(path.clone()))));
+    }
+    if let Some(cmd) = &config.api_key_cmd {
+        return run_key_command(cmd).map(|key| Some((key, ApiKeySource::Command(cmd.clone()))));
+    }
+    Ok(env_var.and_then(|var| {
+        std::env::var(var)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .map(|key| (key, ApiKeySource::Env(var.to_string())))
+    }))
+}
+
+/// 读取 key 文件（支持 `~/` 开头），去掉首尾空白
+fn read_key_file(path: &str) -> Result<String> {
+    let expanded = match (path.strip_prefix("~/"), directories::BaseDirs::new()) {
+        (Some(rest), Some(dirs)) => dirs.home_dir().join(rest),
+        _ => path.into(),
+    };
+    let content = std::fs::read_to_string(&expanded)
+        .map_err(|e| GcopError::Config(format!("Failed to read api_key_file {}: {}", path, e)))?;
+    non_empty(content, || format!("api_key_file {} is empty", path))
+}
+
+/// 通过 shell 执行命令，取 stdout 作为 key
+fn run_key_command(cmd: &str) -> Result<String> {
+    let mut cache = COMMAND_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(key) = cache.get(cmd) {
+        return Ok(key.clone());
+    }
+
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", cmd]).output()
+    } else {
+        Command::new("sh").args(["-c", cmd]).output()
+    }
+    .map_err(|e| GcopError::Config(format!("Failed to run api_key_cmd `{}`: {}", cmd, e)))?;
+
+    if !output.status.success() {
+        // 只报告 stderr，stdout 可能包含部分密钥
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GcopError::Config(format!(
+            "api_key_cmd `{}` failed ({}){}",
+            cmd,
+            output.status,
+            match stderr.trim() {
+                "" => String::new(),
+                message => format!(": {}", message),
+            }
+        )));
+    }
+
+    let key = non_empty(String::from_utf8_lossy(&output.stdout).into_owned(), || {
+        format!("api_key_cmd `{}` printed nothing", cmd)
+    })?;
+    cache.insert(cmd.to_string(), key.clone());
+    Ok(key)
+}
+
+fn non_empty(value: String, message: impl FnOnce() -> String) -> Result<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        Err(GcopError::Config(message()))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn provider() -> ProviderConfig {
+        ProviderConfig {
+            api_style: None,
+            endpoint: None,
+            api_key: None,
+            api_key_file: None,
+            api_key_cmd: None,
+            model: "m".to_string(),
+            max_tokens: None,
+            temperature: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("key");
+        std::fs::write(&file, "sk-file\n").unwrap();
+
+        let mut config = provider();
+        config.api_key_file = Some(file.to_string_lossy().into_owned());
+        config.api_key_cmd = Some("exit 1".to_string());
+        let (key, source) = resolve_api_key(&config, None).unwrap().unwrap();
+        assert_eq!(key, "sk-file");
+        assert_eq!(
+            source,
+            ApiKeySource::File(file.to_string_lossy().into_owned())
+        );
+
+        config.api_key = Some("sk-inline".to_string());
+        let (key, source) = resolve_api_key(&config, None).unwrap().unwrap();
+        assert_eq!(key, "sk-inline");
+        assert_eq!(source.to_string(), "api_key in config");
+
+        assert_eq!(resolve_api_key(&provider(), None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_missing_or_empty_key_file() {
+        let dir = TempDir::new().unwrap();
+        let empty = dir.path().join("empty");
+        std::fs::write(&empty, "  \n").unwrap();
+
+        let mut config = provider();
+        config.api_key_file = Some(empty.to_string_lossy().into_owned());
+        let err = resolve_api_key(&config, None).unwrap_err();
+        assert!(err.to_string().contains("is empty"));
+
+        config.api_key_file = Some(dir.path().join("missing").to_string_lossy().into_owned());
+        assert!(resolve_api_key(&config, None).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_key_command_runs_once() {
+        let dir = TempDir::new().unwrap();
+        let log = dir.path().join("calls");
+        let cmd = format!("echo call >> '{}'; printf 'sk-cmd\\n'", log.display());
+
+        let mut config = provider();
+        config.api_key_cmd = Some(cmd.clone());
+        for _ in 0..2 {
+            let (key, source) = resolve_api_key(&config, None).unwrap().unwrap();
+            assert_eq!(key, "sk-cmd");
+            assert_eq!(source, ApiKeySource::Command(cmd.clone()));
+        }
+        assert_eq!(std::fs::read_to_string(&log).unwrap(), "call\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_key_command_failure_hides_stdout() {
+        let mut config = provider();
+        // 命令本身会出现在错误信息中，用八进制转义让输出与命令文本不同
+        config.api_key_cmd = Some("printf '\\163k-partial'; echo 'locked' >&2; exit 3".to_string());
+        let err = resolve_api_key(&config, None).unwrap_err().to_string();
+        assert!(err.contains("locked"));
+        assert!(!err.contains("sk-partial"));
+    }
+}
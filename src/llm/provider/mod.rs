@@ -1,5 +1,8 @@
 pub mod base;
+pub mod cached;
 pub mod claude;
+pub mod credentials;
+pub mod models;
 pub mod ollama;
 pub mod openai;
 pub mod streaming;
@@ -13,6 +16,8 @@ use reqwest::Client;
 use crate::config::{AppConfig, NetworkConfig};
 use crate::error::{GcopError, Result};
 use crate::llm::LLMProvider;
+use crate::llm::cache::ResponseCache;
+use crate::llm::usage::UsageLog;
 
 /// 创建带有自定义 User-Agent 的 HTTP 客户端
 pub(crate) fn create_http_client(network_config: &NetworkConfig) -> Result<Client> {
@@ -48,18 +53,23 @@ pub fn create_provider(
     // 优先使用 api_style 字段，否则使用 provider 名称（向后兼容）
     let api_style = provider_config.api_style.as_deref().unwrap_or(name);
 
+    let usage_log = UsageLog::from_config(&config.usage);
+
     // 根据 API 风格创建对应的 Provider 实现
     match api_style {
         "claude" => {
-            let provider = claude::ClaudeProvider::new(provider_config, name, &config.network)?;
+            let provider =
+                claude::ClaudeProvider::new(provider_config, name, &config.network, usage_log)?;
             Ok(Arc::new(provider))
         }
         "openai" => {
-            let provider = openai::OpenAIProvider::new(provider_config, name, &config.network)?;
+            let provider =
+                openai::OpenAIProvider::new(provider_config, name, &config.network, usage_log)?;
             Ok(Arc::new(provider))
         }
         "ollama" => {
-            let provider = ollama::OllamaProvider::new(provider_config, name, &config.network)?;
+            let provider =
+                ollama::OllamaProvider::new(provider_config, name, &config.network, usage_log)?;
             Ok(Arc::new(provider))
         }
         _ => Err(GcopError::Config(format!(
@@ -68,3 +78,31 @@ pub fn create_provider(
         ))),
     }
 }
+
+/// 为 provider 包装本地响应缓存
+///
+/// `cache.enabled = false` 时原样返回
+pub fn with_cache(
+    provider: Arc<dyn LLMProvider>,
+    config: &AppConfig,
+    provider_name: Option<&str>,
+) -> Result<Arc<dyn LLMProvider>> {
+    if !config.cache.enabled {
+        return Ok(provider);
+    }
+
+    let name = provider_name.unwrap_or(&config.llm.default_provider);
+    let provider_config = config
+        .llm
+        .providers
+        .get(name)
+        .ok_or_else(|| GcopError::Config(format!("Provider '{}' not found in config", name)))?;
+
+    let cache = ResponseCache::from_config(&config.cache)?;
+    let fingerprint = cached::provider_fingerprint(name, provider_config);
+    Ok(Arc::new(cached::CachedProvider::new(
+        provider,
+        cache,
+        fingerprint,
+    )))
+}
@@ -0,0 +1,149 @@
+//! 查询 provider 的可用模型
+//!
+//! 用于 `init` 向导：Claude 和 OpenAI 读取 `/v1/models`，Ollama 读取 `/api/tags`
+//! （同时用来检测本地 Ollama 是否在运行）。
+
+use reqwest::Client;
+use serde_json::Value;
+
+use super::utils::{DEFAULT_CLAUDE_BASE, DEFAULT_OLLAMA_BASE, DEFAULT_OPENAI_BASE};
+use crate::error::{GcopError, Result};
+
+/// OpenAI 模型列表中与聊天补全无关的模型（名称包含这些片段）
+const OPENAI_NON_CHAT: &[&str] = &[
+    "audio",
+    "realtime",
+    "tts",
+    "transcribe",
+    "whisper",
+    "embedding",
+    "image",
+    "dall-e",
+    "moderation",
+    "search",
+];
+
+/// 各 API 风格的推荐模型，模型列表不可用时作为默认值
+pub fn default_model(api_style: &str) -> &'static str {
+    match api_style {
+        "claude" => "claude-sonnet-4-5-20250929",
+        "openai" => "gpt-4o-mini",
+        _ => "llama3.1:8b",
+    }
+}
+
+/// 查询可用模型，按名称排序
+///
+/// `endpoint` 为 None 时使用官方地址；`api_key` 只用于 Claude 和 OpenAI
+pub async fn list_models(
+    client: &Client,
+    api_style: &str,
+    endpoint: Option<&str>,
+    api_key: Option<&str>,
+) -> Result<Vec<String>> {
+    let (base, path) = match api_style {
+        "claude" => (DEFAULT_CLAUDE_BASE, "/v1/models?limit=100"),
+        "openai" => (DEFAULT_OPENAI_BASE, "/v1/models"),
+        "ollama" => (DEFAULT_OLLAMA_BASE, "/api/tags"),
+        other => {
+            return Err(GcopError::Config(format!(
+                "Unsupported api_style: '{}'",
+                other
+            )));
+        }
+    };
+    let url = format!("{}{}", endpoint.unwrap_or(base).trim_end_matches('/'), path);
+
+    let mut request = client.get(&url);
+    match (api_style, api_key) {
+        ("claude", Some(key)) => {
+            request = request
+                .header("x-api-key", key)
+                .header("anthropic-version", "2023-06-01");
+        }
+        ("openai", Some(key)) => {
+            request = request.header("Authorization", format!("Bearer {}", key));
+        }
+        _ => {}
+    }
+
+    tracing::debug!("Listing models from: {}", url);
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(GcopError::Llm(format!(
+            "Listing models failed ({}): {}",
+            status,
+            response.text().await.unwrap_or_default()
+        )));
+    }
+    let body: Value = response.json().await?;
+    Ok(parse_models(api_style, &body))
+}
+
+/// 从模型列表响应中取出模型名称
+pub fn parse_models(api_style: &str, body: &Value) -> Vec<String> {
+    let (list, field) = match api_style {
+        "ollama" => ("models", "name"),
+        _ => ("data", "id"),
+    };
+    let mut models: Vec<String> = body[list]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item[field].as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    if api_style == "openai" {
+        models.retain(|model| {
+            (model.starts_with("gpt-") || model.starts_with('o') || model.starts_with("chatgpt"))
+                && !OPENAI_NON_CHAT.iter().any(|part| model.contains(part))
+        });
+    }
+    models.sort();
+    models
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_claude_and_ollama_models() {
+        let body =
+            json!({"data": [{"id": "claude-sonnet-4-5-20250929"}, {"id": "claude-haiku-4-5"}]});
+        assert_eq!(
+            parse_models("claude", &body),
+            vec!["claude-haiku-4-5", "claude-sonnet-4-5-20250929"]
+        );
+
+        let body = json!({"models": [{"name": "qwen2.5-coder:7b"}, {"name": "llama3.1:8b"}]});
+        assert_eq!(
+            parse_models("ollama", &body),
+            vec!["llama3.1:8b", "qwen2.5-coder:7b"]
+        );
+
+        assert_eq!(parse_models("ollama", &json!({})), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_parse_openai_keeps_chat_models() {
+        let body = json!({"data": [
+            {"id": "gpt-4o-mini"},
+            {"id": "text-embedding-3-small"},
+            {"id": "gpt-4o-realtime-preview"},
+            {"id": "o3-mini"},
+            {"id": "whisper-1"},
+            {"id": "dall-e-3"},
+            {"id": "gpt-4o-mini-tts"},
+        ]});
+        assert_eq!(
+            parse_models("openai", &body),
+            vec!["gpt-4o-mini", "o3-mini"]
+        );
+    }
+}
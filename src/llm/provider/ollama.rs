@@ -8,16 +8,19 @@ use super::base::{
 use super::utils::{DEFAULT_OLLAMA_BASE, OLLAMA_API_SUFFIX};
 use crate::config::{NetworkConfig, ProviderConfig};
 use crate::error::Result;
+use crate::llm::usage::{TokenUsage, UsageLog, record_usage};
 use crate::llm::{CommitContext, LLMProvider, ReviewResult, ReviewType};
 
 /// Ollama API Provider
 pub struct OllamaProvider {
     client: Client,
+    provider_name: String,
     endpoint: String,
     model: String,
     temperature: Option<f32>,
     max_retries: usize,
     retry_delay_ms: u64,
+    usage_log: Option<UsageLog>,
 }
 
 #[derive(Serialize)]
@@ -40,13 +43,20 @@ struct OllamaResponse {
     response: String,
     #[allow(dead_code)] // 保留用于完整性验证
     done: bool,
+    /// prompt token 数
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    /// 生成 token 数
+    #[serde(default)]
+    eval_count: Option<u64>,
 }
 
 impl OllamaProvider {
     pub fn new(
         config: &ProviderConfig,
-        _provider_name: &str,
+        provider_name: &str,
         network_config: &NetworkConfig,
+        usage_log: Option<UsageLog>,
     ) -> Result<Self> {
         // Ollama 本地部署，无需 API key
         let endpoint = build_endpoint(config, DEFAULT_OLLAMA_BASE, OLLAMA_API_SUFFIX);
@@ -55,11 +65,13 @@ impl OllamaProvider {
 
         Ok(Self {
             client: super::create_http_client(network_config)?,
+            provider_name: provider_name.to_string(),
             endpoint,
             model,
             temperature,
             max_retries: network_config.max_retries,
             retry_delay_ms: network_config.retry_delay_ms,
+            usage_log,
         })
     }
 
@@ -93,6 +105,20 @@ impl OllamaProvider {
         )
         .await?;
 
+        let usage = match (response.prompt_eval_count, response.eval_count) {
+            (None, None) => None,
+            (input, output) => Some(TokenUsage {
+                input_tokens: input.unwrap_or(0),
+                output_tokens: output.unwrap_or(0),
+            }),
+        };
+        record_usage(
+            self.usage_log.as_ref(),
+            &self.provider_name,
+            &self.model,
+            usage,
+        );
+
         Ok(response.response)
     }
 }
@@ -136,6 +162,10 @@ impl LLMProvider for OllamaProvider {
         parse_review_response(&response)
     }
 
+    async fn complete(&self, prompt: &str, spinner: Option<&crate::ui::Spinner>) -> Result<String> {
+        self.call_api(prompt, spinner).await
+    }
+
     fn name(&self) -> &str {
         "ollama"
     }
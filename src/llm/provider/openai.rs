@@ -11,11 +11,13 @@ use super::streaming::process_openai_stream;
 use super::utils::{DEFAULT_OPENAI_BASE, OPENAI_API_SUFFIX};
 use crate::config::{NetworkConfig, ProviderConfig};
 use crate::error::{GcopError, Result};
-use crate::llm::{CommitContext, LLMProvider, ReviewResult, ReviewType, StreamHandle};
+use crate::llm::usage::{TokenUsage, UsageLog, record_usage};
+use crate::llm::{CommitContext, LLMProvider, ReviewResult, ReviewType, StreamChunk, StreamHandle};
 
 /// OpenAI API Provider
 pub struct OpenAIProvider {
     client: Client,
+    provider_name: String,
     api_key: String,
     endpoint: String,
     model: String,
@@ -23,6 +25,9 @@ pub struct OpenAIProvider {
     temperature: f32,
     max_retries: usize,
     retry_delay_ms: u64,
+    usage_log: Option<UsageLog>,
+    /// 流式请求是否携带 `stream_options`（仅官方 endpoint，兼容服务可能拒绝未知字段）
+    stream_usage: bool,
 }
 
 #[derive(Serialize)]
@@ -43,6 +48,14 @@ struct OpenAIStreamRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
     stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+/// 流式请求选项（要求在最后一个 chunk 中返回 usage）
+#[derive(Serialize)]
+struct StreamOptions {
+    include_usage: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -54,6 +67,24 @@ struct MessagePayload {
 #[derive(Deserialize)]
 struct OpenAIResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<OpenAIUsage>,
+}
+
+/// OpenAI usage 字段（流式最后一个 chunk 中也使用相同结构）
+#[derive(Debug, Deserialize)]
+pub struct OpenAIUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl From<OpenAIUsage> for TokenUsage {
+    fn from(usage: OpenAIUsage) -> Self {
+        TokenUsage {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -69,17 +100,20 @@ struct MessageContent {
 impl OpenAIProvider {
     pub fn new(
         config: &ProviderConfig,
-        _provider_name: &str,
+        provider_name: &str,
         network_config: &NetworkConfig,
+        usage_log: Option<UsageLog>,
     ) -> Result<Self> {
         let api_key = extract_api_key(config, "OPENAI_API_KEY", "OpenAI")?;
         let endpoint = build_endpoint(config, DEFAULT_OPENAI_BASE, OPENAI_API_SUFFIX);
         let model = config.model.clone();
         let max_tokens = get_max_tokens_optional(config);
         let temperature = get_temperature(config);
+        let stream_usage = endpoint.starts_with(DEFAULT_OPENAI_BASE);
 
         Ok(Self {
             client: super::create_http_client(network_config)?,
+            provider_name: provider_name.to_string(),
             api_key,
             endpoint,
             model,
@@ -87,6 +121,8 @@ impl OpenAIProvider {
             temperature,
             max_retries: network_config.max_retries,
             retry_delay_ms: network_config.retry_delay_ms,
+            usage_log,
+            stream_usage,
         })
     }
 
@@ -121,6 +157,13 @@ impl OpenAIProvider {
         )
         .await?;
 
+        record_usage(
+            self.usage_log.as_ref(),
+            &self.provider_name,
+            &self.model,
+            response.usage.map(TokenUsage::from),
+        );
+
         response
             .choices
             .into_iter()
@@ -133,7 +176,7 @@ impl OpenAIProvider {
     async fn call_api_streaming(&self, prompt: &str) -> Result<StreamHandle> {
         let (tx, rx) = mpsc::channel(64);
 
-        let request = OpenAIStreamRequest {
+        let mut request = OpenAIStreamRequest {
             model: self.model.clone(),
             messages: vec![MessagePayload {
                 role: "user".to_string(),
@@ -142,6 +185,9 @@ impl OpenAIProvider {
             temperature: self.temperature,
             max_tokens: self.max_tokens,
             stream: true,
+            stream_options: self.stream_usage.then_some(StreamOptions {
+                include_usage: true,
+            }),
         };
 
         tracing::debug!(
@@ -151,17 +197,14 @@ impl OpenAIProvider {
             self.max_tokens
         );
 
-        let auth_header = format!("Bearer {}", self.api_key);
-
-        let response = self
-            .client
-            .post(&self.endpoint)
-            .header("Content-Type", "application/json")
-            .header("Authorization", &auth_header)
-            .json(&request)
-            .send()
-            .await
-            .map_err(GcopError::Network)?;
+        let mut response = self.send_stream_request(&request).await?;
+        // 不支持 stream_options 的服务返回 400 时，去掉该字段重试一次（不再记录用量）
+        if response.status() == reqwest::StatusCode::BAD_REQUEST && request.stream_options.is_some()
+        {
+            tracing::debug!("Streaming request rejected, retrying without stream_options");
+            request.stream_options = None;
+            response = self.send_stream_request(&request).await?;
+        }
 
         if !response.status().is_success() {
             let status = response.status();
@@ -172,17 +215,43 @@ impl OpenAIProvider {
             )));
         }
 
+        let usage_log = self.usage_log.clone();
+        let provider_name = self.provider_name.clone();
+        let model = self.model.clone();
+
         // 在后台任务中处理流
+        // 先记录用量再发送 Done，避免进程在写日志前退出
         // tx 会在任务结束时自动 drop，从而关闭 channel
         tokio::spawn(async move {
-            if let Err(e) = process_openai_stream(response, tx).await {
-                tracing::error!("Stream processing error: {}", e);
+            match process_openai_stream(response, &tx).await {
+                Ok(usage) => {
+                    record_usage(usage_log.as_ref(), &provider_name, &model, usage);
+                    let _ = tx.send(StreamChunk::Done).await;
+                }
+                Err(e) => {
+                    tracing::error!("Stream processing error: {}", e);
+                }
             }
             // tx 在这里被 drop，channel 关闭
         });
 
         Ok(StreamHandle { receiver: rx })
     }
+
+    async fn send_stream_request(
+        &self,
+        request: &OpenAIStreamRequest,
+    ) -> Result<reqwest::Response> {
+        let auth_header = format!("Bearer {}", self.api_key);
+        self.client
+            .post(&self.endpoint)
+            .header("Content-Type", "application/json")
+            .header("Authorization", &auth_header)
+            .json(request)
+            .send()
+            .await
+            .map_err(GcopError::Network)
+    }
 }
 
 #[async_trait]
@@ -224,6 +293,10 @@ impl LLMProvider for OpenAIProvider {
         parse_review_response(&response)
     }
 
+    async fn complete(&self, prompt: &str, spinner: Option<&crate::ui::Spinner>) -> Result<String> {
+        self.call_api(prompt, spinner).await
+    }
+
     fn name(&self) -> &str {
         "openai"
     }
@@ -253,3 +326,83 @@ impl LLMProvider for OpenAIProvider {
         self.call_api_streaming(&prompt).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+    use pretty_assertions::assert_eq;
+
+    fn provider_config(endpoint: Option<String>) -> ProviderConfig {
+        ProviderConfig {
+            api_style: None,
+            endpoint,
+            api_key: Some("sk-test".to_string()),
+            api_key_file: None,
+            api_key_cmd: None,
+            model: "gpt-4o-mini".to_string(),
+            max_tokens: None,
+            temperature: None,
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_stream_usage_only_for_official_endpoint() {
+        let network = NetworkConfig::default();
+        let official = OpenAIProvider::new(&provider_config(None), "openai", &network, None);
+        assert!(official.unwrap().stream_usage);
+
+        let compatible = OpenAIProvider::new(
+            &provider_config(Some("http://localhost:8000".to_string())),
+            "local",
+            &network,
+            None,
+        );
+        assert!(!compatible.unwrap().stream_usage);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_retries_without_stream_options_on_400() {
+        let mut server = mockito::Server::new_async().await;
+        let rejected = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::Regex("include_usage".to_string()))
+            .with_status(400)
+            .with_body(r#"{"error": "unknown field stream_options"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let accepted = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::Regex(r#""stream":true}$"#.to_string()))
+            .with_header("content-type", "text/event-stream")
+            .with_body("data: {\"choices\":[{\"delta\":{\"content\":\"hi\"}}]}\n\ndata: [DONE]\n\n")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut provider = OpenAIProvider::new(
+            &provider_config(Some(server.url())),
+            "openai",
+            &NetworkConfig::default(),
+            None,
+        )
+        .unwrap();
+        provider.stream_usage = true;
+
+        let mut handle = provider.call_api_streaming("hello").await.unwrap();
+        let mut text = String::new();
+        while let Some(chunk) = handle.receiver.recv().await {
+            match chunk {
+                StreamChunk::Delta(delta) => text.push_str(&delta),
+                StreamChunk::Done => break,
+                StreamChunk::Error(e) => panic!("stream error: {}", e),
+            }
+        }
+
+        assert_eq!(text, "hi");
+        rejected.assert_async().await;
+        accepted.assert_async().await;
+    }
+}
@@ -8,16 +8,24 @@ use tokio::sync::mpsc;
 
 use crate::error::{GcopError, Result};
 use crate::llm::StreamChunk;
+use crate::llm::usage::TokenUsage;
+
+use super::openai::OpenAIUsage;
 
 /// OpenAI 流式响应的 delta 结构
 #[derive(Debug, serde::Deserialize)]
 pub struct OpenAIDelta {
+    #[serde(default)]
     pub choices: Vec<OpenAIDeltaChoice>,
+    /// 启用 `stream_options.include_usage` 时，最后一个 chunk 携带用量
+    #[serde(default)]
+    pub usage: Option<OpenAIUsage>,
 }
 
 #[derive(Debug, serde::Deserialize)]
 pub struct OpenAIDeltaChoice {
     pub delta: OpenAIDeltaContent,
+    #[allow(dead_code)] // 保留用于完整性验证
     pub finish_reason: Option<String>,
 }
 
@@ -41,12 +49,19 @@ fn parse_sse_line(line: &str) -> Option<&str> {
 ///
 /// data: [DONE]
 /// ```
+///
+/// `finish_reason` 之后仍继续读取，直到 `[DONE]`，以便拿到最后的 usage chunk。
+/// 结束标记 `StreamChunk::Done` 由调用方发送。
+///
+/// # Returns
+/// 服务端返回的 token 用量（如有）
 pub async fn process_openai_stream(
     response: Response,
-    tx: mpsc::Sender<StreamChunk>,
-) -> Result<()> {
+    tx: &mpsc::Sender<StreamChunk>,
+) -> Result<Option<TokenUsage>> {
     let mut stream = response.bytes_stream();
     let mut buffer = String::new();
+    let mut usage = None;
 
     while let Some(chunk_result) = stream.next().await {
         let chunk: bytes::Bytes = chunk_result.map_err(GcopError::Network)?;
@@ -63,23 +78,20 @@ pub async fn process_openai_stream(
 
             if let Some(data) = parse_sse_line(&line) {
                 if data == "[DONE]" {
-                    let _ = tx.send(StreamChunk::Done).await;
-                    return Ok(());
+                    return Ok(usage);
                 }
 
                 // 解析 JSON
                 match serde_json::from_str::<OpenAIDelta>(data) {
                     Ok(delta) => {
-                        if let Some(choice) = delta.choices.first() {
-                            if let Some(content) = &choice.delta.content
-                                && !content.is_empty()
-                            {
-                                let _ = tx.send(StreamChunk::Delta(content.clone())).await;
-                            }
-                            if choice.finish_reason.is_some() {
-                                let _ = tx.send(StreamChunk::Done).await;
-                                return Ok(());
-                            }
+                        if let Some(u) = delta.usage {
+                            usage = Some(TokenUsage::from(u));
+                        }
+                        if let Some(choice) = delta.choices.first()
+                            && let Some(content) = &choice.delta.content
+                            && !content.is_empty()
+                        {
+                            let _ = tx.send(StreamChunk::Delta(content.clone())).await;
                         }
                     }
                     Err(e) => {
@@ -91,6 +103,5 @@ pub async fn process_openai_stream(
     }
 
     // 流结束但没有收到 [DONE]
-    let _ = tx.send(StreamChunk::Done).await;
-    Ok(())
+    Ok(usage)
 }
@@ -0,0 +1,107 @@
+//! Token 数量估算
+//!
+//! 不依赖具体 tokenizer 的近似估算：ASCII 文本约 4 字符 / token，
+//! CJK 等非 ASCII 字符约 1 字符 / token。用于发送前的预算检查，
+//! 结果会略微偏大，宁可多报也不少报。
+
+/// prompt 模板本身（指令、格式说明等）的近似开销
+pub const PROMPT_OVERHEAD_TOKENS: usize = 600;
+
+/// 已知模型的上下文窗口（按前缀匹配，越具体的前缀越靠前）
+const CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("claude-", 200_000),
+    ("gpt-5", 400_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo", 16_385),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4-mini", 200_000),
+    ("gemini-", 1_048_576),
+    ("deepseek-", 128_000),
+    ("qwen2.5", 32_768),
+    ("llama3.1", 128_000),
+    ("llama3.2", 128_000),
+    ("llama3", 8_192),
+    ("mistral", 32_768),
+    ("codellama", 16_384),
+];
+
+/// 估算文本的 token 数
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut ascii = 0usize;
+    let mut other = 0usize;
+    for c in text.chars() {
+        if c.is_ascii() {
+            ascii += 1;
+        } else {
+            other += 1;
+        }
+    }
+    ascii.div_ceil(4) + other
+}
+
+/// 估算包含 diff 的完整 prompt 的 token 数
+pub fn estimate_prompt_tokens(diff: &str) -> usize {
+    estimate_tokens(diff) + PROMPT_OVERHEAD_TOKENS
+}
+
+/// 查询模型的上下文窗口，未知模型返回 None
+///
+/// 支持带路由前缀的模型名（如 `anthropic/claude-sonnet-4`）
+pub fn context_window(model: &str) -> Option<usize> {
+    let model = model.to_lowercase();
+    let name = model.rsplit('/').next().unwrap_or(&model);
+    CONTEXT_WINDOWS
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, window)| *window)
+}
+
+/// 格式化 token 数（如 `~12.3k tokens`）
+pub fn format_tokens(tokens: usize) -> String {
+    if tokens >= 1000 {
+        format!("~{:.1}k tokens", tokens as f64 / 1000.0)
+    } else {
+        format!("~{} tokens", tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_estimate_tokens_ascii() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_tokens(&"x".repeat(400_000)), 100_000);
+    }
+
+    #[test]
+    fn test_estimate_tokens_cjk_counts_per_char() {
+        assert_eq!(estimate_tokens("修复登录问题"), 6);
+        assert_eq!(estimate_tokens("fix 修复"), 3);
+    }
+
+    #[test]
+    fn test_context_window_prefix_match() {
+        assert_eq!(context_window("claude-sonnet-4-5-20250929"), Some(200_000));
+        assert_eq!(context_window("gpt-4o-mini"), Some(128_000));
+        assert_eq!(context_window("gpt-4-0613"), Some(8_192));
+        assert_eq!(context_window("llama3.1:8b"), Some(128_000));
+        assert_eq!(context_window("llama3:8b"), Some(8_192));
+        assert_eq!(context_window("anthropic/Claude-3-Haiku"), Some(200_000));
+        assert_eq!(context_window("my-custom-model"), None);
+    }
+
+    #[test]
+    fn test_format_tokens() {
+        assert_eq!(format_tokens(512), "~512 tokens");
+        assert_eq!(format_tokens(12_345), "~12.3k tokens");
+    }
+}
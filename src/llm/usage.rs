@@ -0,0 +1,223 @@
+//! Token 用量记录
+//!
+//! 每次 LLM 调用的 token 用量以 JSON Lines 格式追加到本地日志，
+//! 供 `gcop-rs usage` 统计。
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{self, UsageConfig};
+use crate::error::{GcopError, Result};
+
+/// 用量日志文件名
+const USAGE_LOG_FILE: &str = "usage.jsonl";
+
+/// 单次调用的 token 用量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// 用量日志中的一条记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub timestamp: DateTime<Local>,
+    pub provider: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// 本地用量日志
+#[derive(Debug, Clone)]
+pub struct UsageLog {
+    path: PathBuf,
+}
+
+impl UsageLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 默认日志位置（<data_dir>/usage.jsonl）
+    pub fn default_location() -> Result<Self> {
+        let dir = config::get_data_dir()
+            .ok_or_else(|| GcopError::Config("Failed to determine data directory".to_string()))?;
+        Ok(Self::new(dir.join(USAGE_LOG_FILE)))
+    }
+
+    /// 根据配置创建日志（usage.enabled = false 时返回 None）
+    pub fn from_config(usage_config: &UsageConfig) -> Option<Self> {
+        if !usage_config.enabled {
+            return None;
+        }
+        Self::default_location().ok()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 追加一条记录
+    pub fn append(&self, record: &UsageRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    /// 读取所有记录，跳过无法解析的行
+    pub fn read_all(&self) -> Result<Vec<UsageRecord>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)?;
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    tracing::debug!("Skipping malformed usage record: {}", e);
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
+/// 记录一次调用的用量：verbose 模式下输出，并追加到日志
+///
+/// 写日志失败只记录警告，不影响主流程
+pub fn record_usage(
+    log: Option<&UsageLog>,
+    provider: &str,
+    model: &str,
+    usage: Option<TokenUsage>,
+) {
+    let Some(usage) = usage else {
+        tracing::debug!("{} response did not include token usage", provider);
+        return;
+    };
+
+    tracing::debug!(
+        "Token usage ({} / {}): input={}, output={}, total={}",
+        provider,
+        model,
+        usage.input_tokens,
+        usage.output_tokens,
+        usage.total()
+    );
+
+    if let Some(log) = log {
+        let record = UsageRecord {
+            timestamp: Local::now(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+        };
+        if let Err(e) = log.append(&record) {
+            tracing::warn!("Failed to write usage log: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn record(provider: &str, input: u64, output: u64) -> UsageRecord {
+        UsageRecord {
+            timestamp: Local::now(),
+            provider: provider.to_string(),
+            model: "model".to_string(),
+            input_tokens: input,
+            output_tokens: output,
+        }
+    }
+
+    #[test]
+    fn test_append_and_read_all() {
+        let dir = TempDir::new().unwrap();
+        let log = UsageLog::new(dir.path().join("nested").join(USAGE_LOG_FILE));
+
+        assert!(log.read_all().unwrap().is_empty());
+        log.append(&record("claude", 100, 20)).unwrap();
+        log.append(&record("openai", 50, 10)).unwrap();
+
+        let records = log.read_all().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].provider, "claude");
+        assert_eq!(records[1].input_tokens, 50);
+    }
+
+    #[test]
+    fn test_read_all_skips_malformed_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(USAGE_LOG_FILE);
+        let log = UsageLog::new(&path);
+        log.append(&record("claude", 1, 2)).unwrap();
+        fs::write(
+            &path,
+            format!("{}not json\n", fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+
+        assert_eq!(log.read_all().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_record_usage_appends_when_present() {
+        let dir = TempDir::new().unwrap();
+        let log = UsageLog::new(dir.path().join(USAGE_LOG_FILE));
+
+        record_usage(Some(&log), "claude", "m", None);
+        assert!(log.read_all().unwrap().is_empty());
+
+        let usage = TokenUsage {
+            input_tokens: 10,
+            output_tokens: 5,
+        };
+        record_usage(Some(&log), "claude", "m", Some(usage));
+        let records = log.read_all().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].output_tokens, 5);
+    }
+
+    #[test]
+    fn test_from_config_disabled() {
+        let config = UsageConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(UsageLog::from_config(&config).is_none());
+    }
+
+    #[test]
+    fn test_token_usage_total() {
+        let usage = TokenUsage {
+            input_tokens: 3,
+            output_tokens: 4,
+        };
+        assert_eq!(usage.total(), 7);
+    }
+}
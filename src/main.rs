@@ -8,7 +8,7 @@ mod ui;
 
 use anyhow::Result;
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, HookAction};
 use tokio::runtime::Runtime;
 
 fn main() -> Result<()> {
@@ -30,17 +30,23 @@ fn main() -> Result<()> {
         .init();
 
     // 判断是否需要加载配置
-    // config/init/alias 命令不需要完整配置，可以在配置损坏时运行
+    // config/init/alias 命令和 hook 的安装/卸载不需要完整配置，可以在配置损坏时运行
     let needs_config = matches!(
         &cli.command,
-        Commands::Commit { .. } | Commands::Review { .. }
+        Commands::Commit { .. }
+            | Commands::Review { .. }
+            | Commands::Hook {
+                action: HookAction::PrepareCommitMsg { .. }
+                    | HookAction::CommitMsg { .. }
+                    | HookAction::PrePush { .. }
+            }
     );
 
     // 加载配置（管理命令使用默认配置，允许在配置损坏时运行）
     let config = if needs_config {
-        config::load_config()?
+        config::load_config(cli.profile.as_deref())?
     } else {
-        config::load_config().unwrap_or_default()
+        config::load_config(cli.profile.as_deref()).unwrap_or_default()
     };
 
     // 创建 tokio 运行时
@@ -91,9 +97,20 @@ fn main() -> Result<()> {
             Commands::Review {
                 ref target,
                 ref format,
+                ref fail_on,
+                interactive,
+                apply_fixes,
+                update_baseline,
             } => {
+                let options = commands::review::ReviewOptions {
+                    format,
+                    fail_on: fail_on.as_deref(),
+                    interactive,
+                    apply_fixes,
+                    update_baseline,
+                };
                 // 执行 review 命令
-                if let Err(e) = commands::review::run(&cli, &config, target, format).await {
+                if let Err(e) = commands::review::run(&cli, &config, target, &options).await {
                     // 错误处理
                     match e {
                         error::GcopError::UserCancelled => {
@@ -102,8 +119,8 @@ fn main() -> Result<()> {
                         _ => {
                             ui::error(&format!("Error: {}", e), config.ui.colored);
                             if let Some(suggestion) = e.suggestion() {
-                                println!();
-                                println!(
+                                eprintln!();
+                                eprintln!(
                                     "{}",
                                     ui::info(&format!("Tip: {}", suggestion), config.ui.colored)
                                 );
@@ -114,8 +131,19 @@ fn main() -> Result<()> {
                 }
                 Ok(())
             }
-            Commands::Init { force } => {
-                if let Err(e) = commands::init::run(force, config.ui.colored) {
+            Commands::Init {
+                force,
+                provider,
+                model,
+            } => {
+                if let Err(e) = commands::init::run(
+                    force,
+                    provider.as_deref(),
+                    model.as_deref(),
+                    config.ui.colored,
+                )
+                .await
+                {
                     ui::error(&format!("Error: {}", e), config.ui.colored);
                     if let Some(suggestion) = e.suggestion() {
                         println!();
@@ -129,7 +157,9 @@ fn main() -> Result<()> {
                 Ok(())
             }
             Commands::Config { action } => {
-                if let Err(e) = commands::config::run(action, config.ui.colored).await {
+                if let Err(e) =
+                    commands::config::run(action, cli.profile.as_deref(), config.ui.colored).await
+                {
                     ui::error(&format!("Error: {}", e), config.ui.colored);
                     if let Some(suggestion) = e.suggestion() {
                         println!();
@@ -160,6 +190,48 @@ fn main() -> Result<()> {
                 }
                 Ok(())
             }
+            Commands::Hook { ref action } => {
+                if let Err(e) = commands::hook::run(&cli, action, &config).await {
+                    ui::error(&format!("Error: {}", e), config.ui.colored);
+                    if let Some(suggestion) = e.suggestion() {
+                        println!();
+                        println!(
+                            "{}",
+                            ui::info(&format!("Tip: {}", suggestion), config.ui.colored)
+                        );
+                    }
+                    std::process::exit(1);
+                }
+                Ok(())
+            }
+            Commands::Cache { ref action } => {
+                if let Err(e) = commands::cache::run(action, &config) {
+                    ui::error(&format!("Error: {}", e), config.ui.colored);
+                    if let Some(suggestion) = e.suggestion() {
+                        println!();
+                        println!(
+                            "{}",
+                            ui::info(&format!("Tip: {}", suggestion), config.ui.colored)
+                        );
+                    }
+                    std::process::exit(1);
+                }
+                Ok(())
+            }
+            Commands::Usage { days, ref format } => {
+                if let Err(e) = commands::usage::run(days, format, &config) {
+                    ui::error(&format!("Error: {}", e), config.ui.colored);
+                    if let Some(suggestion) = e.suggestion() {
+                        println!();
+                        println!(
+                            "{}",
+                            ui::info(&format!("Tip: {}", suggestion), config.ui.colored)
+                        );
+                    }
+                    std::process::exit(1);
+                }
+                Ok(())
+            }
             Commands::Stats {
                 ref format,
                 ref author,
@@ -1,6 +1,7 @@
 use colored::Colorize;
 
 use crate::git::DiffStats;
+use crate::llm::tokens::format_tokens;
 
 /// 显示成功消息（绿色 ✓）
 pub fn success(msg: &str, colored: bool) {
@@ -29,6 +30,17 @@ pub fn warning(msg: &str, colored: bool) {
     }
 }
 
+/// 显示警告消息到 stderr（黄色 ⚠）
+///
+/// 用于可能出现在机器可读输出（如 `review --format json`）之前的警告，stdout 只留给报告
+pub fn warning_stderr(msg: &str, colored: bool) {
+    if colored {
+        eprintln!("{} {}", "⚠".yellow().bold(), msg.yellow());
+    } else {
+        eprintln!("⚠ {}", msg);
+    }
+}
+
 /// 显示信息消息（蓝色 ℹ）
 pub fn info(msg: &str, colored: bool) -> String {
     if colored {
@@ -86,6 +98,16 @@ pub fn format_diff_stats(stats: &DiffStats, colored: bool) -> String {
     }
 }
 
+/// 格式化 diff 预览：统计信息 + 估算的 prompt 大小
+pub fn format_diff_preview(stats: &DiffStats, tokens: Option<usize>, colored: bool) -> String {
+    let summary = format_diff_stats(stats, colored);
+    match tokens {
+        Some(tokens) if colored => format!("{}, {}", summary, format_tokens(tokens).dimmed()),
+        Some(tokens) => format!("{}, {}", summary, format_tokens(tokens)),
+        None => summary,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,4 +221,21 @@ mod tests {
         let result = format_diff_stats(&stats, false);
         assert!(result.contains("0 files")); // 复数形式用于0
     }
+
+    #[test]
+    fn test_format_diff_preview_with_tokens() {
+        let stats = DiffStats {
+            files_changed: vec!["test.rs".to_string()],
+            insertions: 10,
+            deletions: 5,
+        };
+        assert_eq!(
+            format_diff_preview(&stats, Some(12_345), false),
+            "1 file changed, 10 insertions(+), 5 deletions(-), ~12.3k tokens"
+        );
+        assert_eq!(
+            format_diff_preview(&stats, None, false),
+            format_diff_stats(&stats, false)
+        );
+    }
 }
@@ -22,3 +22,78 @@ pub fn edit_text(initial_content: &str) -> Result<String> {
     // 返回编辑后的内容（保留用户的格式）
     Ok(edited)
 }
+
+/// 在编辑器中打开文件并跳到指定行
+///
+/// 编辑器取 `$VISUAL` / `$EDITOR`（可带参数），都未设置时由 edit crate 查找系统默认编辑器
+pub fn open_at_line(path: &str, line: Option<usize>) -> Result<()> {
+    let command = std::env::var("VISUAL")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|v| !v.trim().is_empty());
+    let (program, mut args) = match command {
+        Some(command) => {
+            let mut parts = command.split_whitespace().map(String::from);
+            let program = parts.next().unwrap_or_default();
+            (program, parts.collect::<Vec<_>>())
+        }
+        None => {
+            let editor =
+                edit::get_editor().map_err(|e| GcopError::Other(format!("Editor error: {}", e)))?;
+            (editor.to_string_lossy().into_owned(), Vec::new())
+        }
+    };
+    args.extend(goto_args(&program, path, line));
+
+    let status = std::process::Command::new(&program)
+        .args(&args)
+        .status()
+        .map_err(|e| GcopError::Other(format!("Failed to start editor '{}': {}", program, e)))?;
+    if !status.success() {
+        return Err(GcopError::Other(format!(
+            "Editor '{}' exited with {}",
+            program, status
+        )));
+    }
+    Ok(())
+}
+
+/// 跳转到指定行的参数：VS Code 系用 `--goto file:line`，Sublime / Zed / Helix 用 `file:line`，
+/// 其余（vim、nano、emacs 等）用 `+line file`
+fn goto_args(program: &str, path: &str, line: Option<usize>) -> Vec<String> {
+    let Some(line) = line else {
+        return vec![path.to_string()];
+    };
+    let name = std::path::Path::new(program)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match name.as_str() {
+        "code" | "code-insiders" | "codium" | "cursor" => {
+            vec!["--goto".to_string(), format!("{}:{}", path, line)]
+        }
+        "subl" | "zed" | "hx" | "helix" => vec![format!("{}:{}", path, line)],
+        _ => vec![format!("+{}", line), path.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_goto_args() {
+        assert_eq!(
+            goto_args("vim", "src/a.rs", Some(7)),
+            vec!["+7", "src/a.rs"]
+        );
+        assert_eq!(
+            goto_args("/usr/bin/code", "src/a.rs", Some(7)),
+            vec!["--goto", "src/a.rs:7"]
+        );
+        assert_eq!(goto_args("hx", "src/a.rs", Some(7)), vec!["src/a.rs:7"]);
+        assert_eq!(goto_args("nano", "src/a.rs", None), vec!["src/a.rs"]);
+    }
+}
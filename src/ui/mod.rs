@@ -6,6 +6,9 @@ pub mod streaming;
 
 pub use colors::*;
 pub use editor::*;
-pub use prompt::{CommitAction, commit_action_menu, confirm, get_retry_feedback};
+pub use prompt::{
+    CommitAction, SecretAction, TriageAction, commit_action_menu, confirm, get_followup_question,
+    get_retry_feedback, secret_action_menu, triage_action_menu,
+};
 pub use spinner::*;
 pub use streaming::*;
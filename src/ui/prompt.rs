@@ -186,6 +186,181 @@ pub fn get_retry_feedback(colored: bool) -> Result<Option<String>> {
     }
 }
 
+/// 检测到密钥后用户可选的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretAction {
+    Redact,     // 脱敏后继续
+    SendAnyway, // 原样发送
+    Abort,      // 中止
+}
+
+/// 显示密钥处理选项菜单
+///
+/// # Returns
+/// * `Ok(SecretAction)` - 用户选择的操作（ESC 视为中止）
+pub fn secret_action_menu(colored: bool) -> Result<SecretAction> {
+    let options: Vec<String> = if colored {
+        vec![
+            format!(
+                "{} {}",
+                "✎".green().bold(),
+                "Redact - Replace detected secrets and continue".green()
+            ),
+            format!(
+                "{} {}",
+                "⚠".yellow().bold(),
+                "Send anyway - These are not real secrets".yellow()
+            ),
+            format!(
+                "{} {}",
+                "✕".red().bold(),
+                "Abort - Fix the diff first".red()
+            ),
+        ]
+    } else {
+        vec![
+            "✎ Redact - Replace detected secrets and continue".to_string(),
+            "⚠ Send anyway - These are not real secrets".to_string(),
+            "✕ Abort - Fix the diff first".to_string(),
+        ]
+    };
+
+    let prompt = if colored {
+        format!("{}", "How should these be handled?".cyan().bold())
+    } else {
+        "How should these be handled?".to_string()
+    };
+
+    let selection = Select::new()
+        .with_prompt(prompt)
+        .items(&options)
+        .default(0)
+        .interact_opt()
+        .map_err(|_| GcopError::UserCancelled)?;
+
+    Ok(match selection {
+        Some(0) => SecretAction::Redact,
+        Some(1) => SecretAction::SendAnyway,
+        _ => SecretAction::Abort,
+    })
+}
+
+/// 审查问题分诊时的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriageAction {
+    Accept,        // 确认问题，需要修复
+    Dismiss,       // 不处理
+    FalsePositive, // 误报
+    FollowUp,      // 向模型追问
+    OpenEditor,    // 在编辑器中打开
+    ApplyFix,      // 应用建议的修复
+    Skip,          // 暂不决定
+    Quit,          // 结束分诊
+}
+
+/// 显示审查问题分诊菜单
+///
+/// # Arguments
+/// * `can_open` - 问题是否带文件位置（决定是否显示"在编辑器中打开"）
+/// * `can_fix` - 是否有可应用的修复补丁
+///
+/// # Returns
+/// * `Ok(TriageAction)` - 用户选择的操作（ESC 视为结束）
+pub fn triage_action_menu(can_open: bool, can_fix: bool, colored: bool) -> Result<TriageAction> {
+    let mut entries = Vec::new();
+    if can_fix {
+        entries.push((
+            TriageAction::ApplyFix,
+            "⚒",
+            "Apply fix - Apply the suggested patch",
+        ));
+    }
+    entries.extend([
+        (TriageAction::Accept, "✓", "Accept - Real issue, will fix"),
+        (TriageAction::Dismiss, "–", "Dismiss - Won't fix"),
+        (
+            TriageAction::FalsePositive,
+            "✕",
+            "False positive - Not an issue",
+        ),
+        (
+            TriageAction::FollowUp,
+            "?",
+            "Ask - Follow-up question to the model",
+        ),
+    ]);
+    if can_open {
+        entries.push((
+            TriageAction::OpenEditor,
+            "✎",
+            "Open - Jump to the line in $EDITOR",
+        ));
+    }
+    entries.push((TriageAction::Skip, "→", "Skip - Decide later"));
+    entries.push((TriageAction::Quit, "■", "Quit - Stop triage"));
+
+    let options: Vec<String> = entries
+        .iter()
+        .map(|(action, icon, label)| {
+            if !colored {
+                return format!("{} {}", icon, label);
+            }
+            match action {
+                TriageAction::Accept | TriageAction::ApplyFix => {
+                    format!("{} {}", icon.green().bold(), label.green())
+                }
+                TriageAction::Dismiss | TriageAction::Skip => {
+                    format!("{} {}", icon.dimmed(), label)
+                }
+                TriageAction::FalsePositive => {
+                    format!("{} {}", icon.yellow().bold(), label.yellow())
+                }
+                TriageAction::FollowUp | TriageAction::OpenEditor => {
+                    format!("{} {}", icon.blue().bold(), label.blue())
+                }
+                TriageAction::Quit => format!("{} {}", icon.red().bold(), label.red()),
+            }
+        })
+        .collect();
+
+    let prompt = if colored {
+        format!(
+            "{} {}",
+            "Triage this issue:".cyan().bold(),
+            "(ESC to quit)".dimmed()
+        )
+    } else {
+        "Triage this issue (ESC to quit):".to_string()
+    };
+
+    let selection = Select::new()
+        .with_prompt(prompt)
+        .items(&options)
+        .default(0)
+        .interact_opt()
+        .map_err(|_| GcopError::UserCancelled)?;
+
+    Ok(selection
+        .map(|idx| entries[idx].0)
+        .unwrap_or(TriageAction::Quit))
+}
+
+/// 读取对审查问题的追问
+///
+/// # Returns
+/// * `Ok(Some(String))` - 用户输入的问题
+/// * `Ok(None)` - 用户未输入
+pub fn get_followup_question() -> Result<Option<String>> {
+    let question: String = Input::new()
+        .with_prompt("Question")
+        .allow_empty(true)
+        .interact_text()
+        .map_err(|_| GcopError::UserCancelled)?;
+
+    let trimmed = question.trim();
+    Ok((!trimmed.is_empty()).then(|| trimmed.to_string()))
+}
+
 /// 交互式确认提示
 ///
 /// # Arguments